@@ -0,0 +1,46 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use exp_golomb::{unsigned_sequence_len_bits, ExpGolombDecoder, ExpGolombEncoder};
+use rand::{Rng, SeedableRng};
+
+const SEED: u64 = 0;
+const NUM_VALS: usize = 10_000;
+
+fn encode(nums: &[u64]) -> Vec<u8> {
+    let mut buf = vec![0u8; unsigned_sequence_len_bits(nums).div_ceil(8)];
+    let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    for &num in nums {
+        writer.put_unsigned(num).unwrap();
+    }
+    writer.close();
+    buf
+}
+
+fn decode_all(buf: &[u8], count: usize) {
+    let mut reader = ExpGolombDecoder::new(buf, 0).unwrap();
+    for _ in 0..count {
+        black_box(reader.next_unsigned());
+    }
+}
+
+fn bench_decode(c: &mut Criterion) {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(SEED);
+    let mut group = c.benchmark_group("decode");
+    group.throughput(Throughput::Elements(NUM_VALS as u64));
+
+    for (name, max) in [
+        ("zeros", 1),
+        ("zeros_and_ones", 2),
+        ("small", 1 << 4),
+        ("medium", 1 << 16),
+        ("large", u64::MAX),
+    ] {
+        let nums: Vec<_> = (0..NUM_VALS).map(|_| rng.gen_range(0..max)).collect();
+        let buf = encode(&nums);
+        group.bench_function(name, |b| b.iter(|| decode_all(&buf, NUM_VALS)));
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_decode);
+criterion_main!(benches);
@@ -0,0 +1,34 @@
+#![no_main]
+
+use exp_golomb::{ExpGolombDecoder, ExpGolombEncoder};
+use libfuzzer_sys::fuzz_target;
+
+// Longest codeword is 129 bits for `u64::MAX`
+const MAX_CODEWORD_BYTES: usize = 17;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&start, buf)) = data.split_first() else {
+        return;
+    };
+    let Some(mut reader) = ExpGolombDecoder::new(buf, u32::from(start % 8)) else {
+        return;
+    };
+
+    let mut values = Vec::new();
+    while let Some(value) = reader.next_unsigned() {
+        values.push(value);
+    }
+
+    // Re-encoding the decoded values then decoding them again must give the same values
+    let mut encoded = vec![0u8; MAX_CODEWORD_BYTES * values.len() + 1];
+    let mut writer = ExpGolombEncoder::new(&mut encoded, 0).unwrap();
+    for &value in &values {
+        writer.put_unsigned(value).unwrap();
+    }
+    writer.close();
+
+    let mut reader = ExpGolombDecoder::new(&encoded, 0).unwrap();
+    for &value in &values {
+        assert_eq!(reader.next_unsigned(), Some(value));
+    }
+});
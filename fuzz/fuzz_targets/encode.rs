@@ -0,0 +1,27 @@
+#![no_main]
+
+use exp_golomb::{ExpGolombDecoder, ExpGolombEncoder};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (u8, u8, Vec<u64>)| {
+    let (start, len, values) = input;
+    let start = u32::from(start % 8);
+    // Deliberately small buffer so that running out of room is exercised
+    let mut buf = vec![0u8; usize::from(len) + 1];
+
+    let mut writer = ExpGolombEncoder::new(&mut buf, start).unwrap();
+    let mut written = 0;
+    for &value in &values {
+        if writer.put_unsigned(value).is_none() {
+            break;
+        }
+        written += 1;
+    }
+    writer.close();
+
+    // Everything that was successfully written must decode back
+    let mut reader = ExpGolombDecoder::new(&buf, start).unwrap();
+    for &value in &values[..written] {
+        assert_eq!(reader.next_unsigned(), Some(value));
+    }
+});
@@ -0,0 +1,227 @@
+use crate::decoder::{count_leading_zeroes, read_unsigned, unsigned_to_signed};
+
+/// A source of bits for [`ExpGolombSourceDecoder`].
+///
+/// Implement this to decode Exp-Golomb values from anything that can produce bits one at a time.
+/// The trait is object safe so `&mut dyn BitSource` can be used for sources only known at runtime.
+pub trait BitSource {
+    /// Returns the next bit as `0` or `1`, or `None` if there are no more bits.
+    fn next_bit(&mut self) -> Option<u8>;
+}
+
+impl<S: BitSource + ?Sized> BitSource for &mut S {
+    #[inline]
+    fn next_bit(&mut self) -> Option<u8> {
+        (**self).next_bit()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: BitSource + ?Sized> BitSource for Box<S> {
+    #[inline]
+    fn next_bit(&mut self) -> Option<u8> {
+        (**self).next_bit()
+    }
+}
+
+/// A [`BitSource`] that reads up to a fixed number of `0` bits past the end of another source, like
+/// decoders that treat the bits after the end of the data as zeros. A codeword whose suffix runs
+/// slightly past the end can then still be decoded. The padding is bounded so that the zeros
+/// cannot form an endless prefix.
+///
+/// # Examples
+///
+/// ```
+/// use exp_golomb::{ExpGolombDecoder, ExpGolombSourceDecoder, ZeroPaddedSource};
+///
+/// // 0001 - the last three bits of 0001000 - 7 are missing
+/// let data = [0b11110001];
+/// let mut strict = ExpGolombDecoder::new(&data, 4).unwrap();
+/// assert_eq!(strict.next_unsigned(), None);
+///
+/// let reader = ExpGolombDecoder::new(&data, 4).unwrap();
+/// let mut lenient = ExpGolombSourceDecoder::new(ZeroPaddedSource::new(reader, 8));
+/// assert_eq!(lenient.next_unsigned(), Some(7));
+/// assert_eq!(lenient.into_inner().padded_bits(), 3);
+/// ```
+pub struct ZeroPaddedSource<S> {
+    source: S,
+    max_padding: u32,
+    padded_bits: u32,
+}
+
+impl<S: BitSource> ZeroPaddedSource<S> {
+    /// Create a new `ZeroPaddedSource` that reads at most `max_padding` zeros after `source` runs
+    /// out of bits.
+    #[inline]
+    pub fn new(source: S, max_padding: u32) -> ZeroPaddedSource<S> {
+        ZeroPaddedSource {
+            source,
+            max_padding,
+            padded_bits: 0,
+        }
+    }
+
+    /// Returns the number of zeros read past the end of the underlying source so far.
+    #[inline]
+    pub fn padded_bits(&self) -> u32 {
+        self.padded_bits
+    }
+
+    /// Consumes the `ZeroPaddedSource`, returning the underlying source.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+}
+
+impl<S: BitSource> BitSource for ZeroPaddedSource<S> {
+    #[inline]
+    fn next_bit(&mut self) -> Option<u8> {
+        if let Some(bit) = self.source.next_bit() {
+            return Some(bit);
+        }
+        if self.padded_bits == self.max_padding {
+            return None;
+        }
+        self.padded_bits += 1;
+        Some(0)
+    }
+}
+
+/// An Exponential-Golomb parser over any [`BitSource`].
+///
+/// # Examples
+///
+/// ```
+/// use exp_golomb::{BitSource, ExpGolombSourceDecoder};
+///
+/// // Produces the bits of `value` starting from the least significant one
+/// struct LsbFirst {
+///     value: u32,
+///     remaining: u32,
+/// }
+///
+/// impl BitSource for LsbFirst {
+///     fn next_bit(&mut self) -> Option<u8> {
+///         self.remaining = self.remaining.checked_sub(1)?;
+///         let bit = (self.value & 1) as u8;
+///         self.value >>= 1;
+///         Some(bit)
+///     }
+/// }
+///
+/// // 00110 - 5
+/// let source = LsbFirst { value: 0b01100, remaining: 5 };
+/// let mut reader = ExpGolombSourceDecoder::new(source);
+/// assert_eq!(reader.next_unsigned(), Some(5));
+/// assert_eq!(reader.next_unsigned(), None);
+/// ```
+pub struct ExpGolombSourceDecoder<S> {
+    source: S,
+}
+
+impl<S: BitSource> ExpGolombSourceDecoder<S> {
+    /// Create a new `ExpGolombSourceDecoder` reading from `source`.
+    #[inline]
+    pub fn new(source: S) -> ExpGolombSourceDecoder<S> {
+        ExpGolombSourceDecoder { source }
+    }
+
+    /// Consumes the `ExpGolombSourceDecoder`, returning the underlying source.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+
+    /// Read the next bit (i.e, as a flag). Returns `None` if the source has no more bits.
+    #[inline]
+    pub fn next_bit(&mut self) -> Option<u8> {
+        self.source.next_bit()
+    }
+
+    /// Read the next Exp-Golomb value as an unsigned integer. Returns `None` if the source runs out
+    /// of bits before parsing is completed or if the coded value is exceeds the limits of a `u64`.
+    #[inline]
+    #[must_use = "use `ExpGolombSourceDecoder::skip_next` if the value is not needed"]
+    pub fn next_unsigned(&mut self) -> Option<u64> {
+        read_unsigned(&mut self.source)
+    }
+
+    /// Read the next Exp-Golomb value, interpreting it as a signed integer. Returns `None` if the
+    /// source runs out of bits before parsing is completed or if the coded value is exceeds the
+    /// limits of a `i64`.
+    #[inline]
+    #[must_use = "use `ExpGolombSourceDecoder::skip_next` if the value is not needed"]
+    pub fn next_signed(&mut self) -> Option<i64> {
+        self.next_unsigned().map(unsigned_to_signed)
+    }
+
+    /// Skip the next Exp-Golomb encoded value. Any parsing error at the end of the source is
+    /// ignored.
+    #[inline]
+    pub fn skip_next(&mut self) {
+        if let Some(lz) = count_leading_zeroes(&mut self.source, u64::BITS) {
+            for _ in 0..lz {
+                if self.source.next_bit().is_none() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_util, ExpGolombDecoder};
+
+    struct Bits<'a>(&'a [u8]);
+
+    impl BitSource for Bits<'_> {
+        fn next_bit(&mut self) -> Option<u8> {
+            let (&bit, rest) = self.0.split_first()?;
+            self.0 = rest;
+            Some(bit)
+        }
+    }
+
+    #[test]
+    fn dyn_source() {
+        // 010 00110 1
+        let mut source = Bits(&[0, 1, 0, 0, 0, 1, 1, 0, 1]);
+        let mut reader = ExpGolombSourceDecoder::new(&mut source as &mut dyn BitSource);
+        assert_eq!(reader.next_unsigned(), Some(1));
+        reader.skip_next();
+        assert_eq!(reader.next_signed(), Some(0));
+        assert_eq!(reader.next_bit(), None);
+    }
+
+    #[test]
+    fn zero_padding_is_bounded() {
+        // 00000001 - 127 with the whole suffix missing
+        let data = [0b00000001];
+        for max_padding in 0..10 {
+            let reader = ExpGolombDecoder::new(&data, 0).unwrap();
+            let mut reader =
+                ExpGolombSourceDecoder::new(ZeroPaddedSource::new(reader, max_padding));
+            if max_padding < 7 {
+                assert_eq!(reader.next_unsigned(), None);
+            } else {
+                assert_eq!(reader.next_unsigned(), Some(127));
+                // The rest of the padding is not enough for another codeword
+                assert_eq!(reader.next_unsigned(), None);
+            }
+            assert_eq!(reader.into_inner().padded_bits(), max_padding);
+        }
+    }
+
+    #[test]
+    fn slice_decoder_as_source() {
+        for start in 0..=7 {
+            let reader = ExpGolombDecoder::new(&test_util::DATA, start).unwrap();
+            let mut reader = ExpGolombSourceDecoder::new(reader);
+            test_util::assert_matches_contiguous(start, || reader.next_unsigned());
+        }
+    }
+}
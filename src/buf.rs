@@ -0,0 +1,119 @@
+use crate::bit_source::BitSource;
+use bytes::Buf;
+
+/// A [`BitSource`] that reads bytes from a [`bytes::Buf`] as they are needed.
+///
+/// The `Buf` is advanced one byte at a time so its chunks do not have to be contiguous. The byte
+/// containing the next bit to be read has already been taken out of the `Buf`, which means that
+/// [`BufBitSource::into_inner`] returns a `Buf` starting at the following byte.
+///
+/// # Examples
+///
+/// ```
+/// use bytes::{Buf, Bytes};
+/// use exp_golomb::{BufBitSource, ExpGolombSourceDecoder};
+///
+/// // 00000000111111111 - 510, split across two chunks
+/// let first = Bytes::from_static(&[0b00000000]);
+/// let second = Bytes::from_static(&[0b11111111, 0b10000000, 0xAB]);
+///
+/// let source = BufBitSource::new(first.chain(second), 0).unwrap();
+/// let mut reader = ExpGolombSourceDecoder::new(source);
+/// assert_eq!(reader.next_unsigned(), Some(510));
+///
+/// let rest = reader.into_inner().into_inner();
+/// assert_eq!(rest.chunk(), [0xAB]);
+/// ```
+pub struct BufBitSource<B> {
+    buf: B,
+    byte: u8,
+    bit_pos: u32,
+}
+
+impl<B: Buf> BufBitSource<B> {
+    /// Create a new `BufBitSource`, taking the first byte out of `buf`.
+    ///
+    /// `start` denotes the starting position in the first byte and goes from 0 (first) to 7
+    /// (last). This function returns `None` if `buf` is empty or if `start` is not within
+    /// \[0, 7\].
+    #[inline]
+    #[must_use]
+    pub fn new(mut buf: B, start: u32) -> Option<BufBitSource<B>> {
+        if !buf.has_remaining() || start > 7 {
+            return None;
+        }
+        let byte = buf.get_u8();
+        Some(BufBitSource {
+            buf,
+            byte,
+            bit_pos: start,
+        })
+    }
+
+    /// Returns the position of the next bit within the last byte taken from the `Buf`. This is 8 if
+    /// all of its bits have been read.
+    #[inline]
+    pub fn bit_pos(&self) -> u32 {
+        self.bit_pos
+    }
+
+    /// Consumes the `BufBitSource`, returning the underlying `Buf`. Any unread bits of the last
+    /// byte taken from it are lost.
+    #[inline]
+    pub fn into_inner(self) -> B {
+        self.buf
+    }
+}
+
+impl<B: Buf> BitSource for BufBitSource<B> {
+    #[inline]
+    fn next_bit(&mut self) -> Option<u8> {
+        if self.bit_pos == 8 {
+            if !self.buf.has_remaining() {
+                return None;
+            }
+            self.byte = self.buf.get_u8();
+            self.bit_pos = 0;
+        }
+        let bit = (self.byte >> (7 - self.bit_pos)) & 1;
+        self.bit_pos += 1;
+        Some(bit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_util, ExpGolombSourceDecoder};
+
+    #[test]
+    fn empty_buf() {
+        assert!(BufBitSource::new(&[][..], 0).is_none());
+        assert!(BufBitSource::new(&[0u8][..], 8).is_none());
+    }
+
+    #[test]
+    fn remaining_bytes_after_partial_byte() {
+        // 010 - 1, leaving the rest of the first byte unread
+        let first = &[0b01000110][..];
+        let second = &[0xAB, 0xCD][..];
+        let source = BufBitSource::new(first.chain(second), 0).unwrap();
+        let mut reader = ExpGolombSourceDecoder::new(source);
+        assert_eq!(reader.next_unsigned(), Some(1));
+
+        let source = reader.into_inner();
+        assert_eq!(source.bit_pos(), 3);
+        let rest = source.into_inner();
+        assert_eq!(rest.remaining(), 2);
+        assert_eq!(rest.chunk(), [0xAB, 0xCD]);
+    }
+
+    #[test]
+    fn matches_contiguous_decoder() {
+        test_util::for_each_split(|first, second, start| {
+            let source = BufBitSource::new(first.chain(second), start).unwrap();
+            let mut chained = ExpGolombSourceDecoder::new(source);
+            test_util::assert_matches_contiguous(start, || chained.next_unsigned());
+        });
+    }
+}
@@ -0,0 +1,162 @@
+use crate::bit_source::BitSource;
+
+/// The order of the bytes within each word of a bitstream. The bits of each byte are read
+/// MSB-first in either case.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ByteOrder {
+    /// The bytes are read in the order they appear in the buffer.
+    BigEndian,
+    /// The bytes of each word are read starting from the last one, as if each word had been
+    /// byte-swapped first.
+    LittleEndian,
+}
+
+/// A [`BitSource`] that reads the bytes of a slice in the given [`ByteOrder`], e.g. for streams
+/// produced by a DSP that stores little-endian words but expects their bits to be read MSB-first
+/// after a byte-swap.
+///
+/// # Examples
+///
+/// ```
+/// use exp_golomb::{ByteOrder, ByteOrderBitSource, ExpGolombSourceDecoder};
+///
+/// // 00000000111111111 - 510, stored as little-endian 16-bit words
+/// let data = [0b11111111, 0b00000000, 0b00000000, 0b10000000];
+/// let source = ByteOrderBitSource::new(&data, 0, ByteOrder::LittleEndian, 2).unwrap();
+/// let mut reader = ExpGolombSourceDecoder::new(source);
+/// assert_eq!(reader.next_unsigned(), Some(510));
+/// ```
+pub struct ByteOrderBitSource<'a> {
+    buf: &'a [u8],
+    order: ByteOrder,
+    word_len: usize,
+    index: usize,
+    bit_pos: u32,
+}
+
+impl<'a> ByteOrderBitSource<'a> {
+    /// Create a new `ByteOrderBitSource` over words of `word_len` bytes.
+    ///
+    /// `start` denotes the starting position in the first byte to be read and goes from 0 (first)
+    /// to 7 (last). This function returns `None` if `buf` is empty, if `start` is not within
+    /// \[0, 7\] or if the length of `buf` is not a non-zero multiple of `word_len`.
+    #[inline]
+    #[must_use]
+    pub fn new(
+        buf: &'a [u8],
+        start: u32,
+        order: ByteOrder,
+        word_len: usize,
+    ) -> Option<ByteOrderBitSource<'a>> {
+        if buf.is_empty() || start > 7 || word_len == 0 || !buf.len().is_multiple_of(word_len) {
+            return None;
+        }
+        Some(ByteOrderBitSource {
+            buf,
+            order,
+            word_len,
+            index: 0,
+            bit_pos: start,
+        })
+    }
+
+    /// Returns the number of bits read so far, counting the skipped bits of the first byte.
+    #[inline]
+    pub fn bit_offset(&self) -> usize {
+        self.index * 8 + self.bit_pos as usize
+    }
+}
+
+impl BitSource for ByteOrderBitSource<'_> {
+    #[inline]
+    fn next_bit(&mut self) -> Option<u8> {
+        let index = match self.order {
+            ByteOrder::BigEndian => self.index,
+            ByteOrder::LittleEndian => {
+                let word_start = self.index - self.index % self.word_len;
+                word_start + self.word_len - 1 - self.index % self.word_len
+            }
+        };
+        let bit = (self.buf.get(index)? >> (7 - self.bit_pos)) & 1;
+        self.bit_pos += 1;
+        if self.bit_pos == 8 {
+            self.bit_pos = 0;
+            self.index += 1;
+        }
+        Some(bit)
+    }
+}
+
+/// Reverse the bytes of each `word_len`-byte word of `buf` in place. Encoding with
+/// [`ExpGolombEncoder`](crate::ExpGolombEncoder) and then calling this produces a stream that
+/// [`ByteOrderBitSource`] reads back with [`ByteOrder::LittleEndian`]. Returns `None` if the length
+/// of `buf` is not a multiple of `word_len` or if `word_len` is zero, in which case nothing is
+/// changed.
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::swap_word_bytes;
+/// let mut buf = [1, 2, 3, 4, 5, 6];
+/// swap_word_bytes(&mut buf, 2).unwrap();
+/// assert_eq!(buf, [2, 1, 4, 3, 6, 5]);
+/// assert!(swap_word_bytes(&mut buf, 4).is_none());
+/// ```
+#[inline]
+pub fn swap_word_bytes(buf: &mut [u8], word_len: usize) -> Option<()> {
+    if word_len == 0 || !buf.len().is_multiple_of(word_len) {
+        return None;
+    }
+    for word in buf.chunks_exact_mut(word_len) {
+        word.reverse();
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ExpGolombDecoder, ExpGolombEncoder, ExpGolombSourceDecoder};
+
+    #[test]
+    fn invalid_word_len() {
+        assert!(ByteOrderBitSource::new(&[0; 4], 0, ByteOrder::LittleEndian, 0).is_none());
+        assert!(ByteOrderBitSource::new(&[0; 4], 0, ByteOrder::LittleEndian, 3).is_none());
+        assert!(ByteOrderBitSource::new(&[0; 4], 8, ByteOrder::BigEndian, 4).is_none());
+        assert!(ByteOrderBitSource::new(&[], 0, ByteOrder::BigEndian, 1).is_none());
+    }
+
+    #[test]
+    fn round_trip_swapped_words() {
+        let values: [u64; 8] = [0, 1, 510, 4, u64::MAX, 7, 1 << 40, 3];
+        for word_len in [1, 2, 4, 8] {
+            for start in 0..=7 {
+                let mut buf = [0u8; 48];
+                let mut writer = ExpGolombEncoder::new(&mut buf, start).unwrap();
+                for &value in &values {
+                    writer.put_unsigned(value).unwrap();
+                }
+                writer.close();
+
+                let source = ByteOrderBitSource::new(&buf, start, ByteOrder::BigEndian, word_len);
+                let mut big = ExpGolombSourceDecoder::new(source.unwrap());
+                let mut contiguous = ExpGolombDecoder::new(&buf, start).unwrap();
+
+                let mut swapped = buf;
+                swap_word_bytes(&mut swapped, word_len).unwrap();
+                let source =
+                    ByteOrderBitSource::new(&swapped, start, ByteOrder::LittleEndian, word_len);
+                let mut little = ExpGolombSourceDecoder::new(source.unwrap());
+
+                for &value in &values {
+                    assert_eq!(contiguous.next_unsigned(), Some(value));
+                    assert_eq!(big.next_unsigned(), Some(value));
+                    assert_eq!(little.next_unsigned(), Some(value));
+                }
+                let pos = contiguous.position();
+                let offset = pos.byte_index * 8 + pos.bit_offset as usize;
+                assert_eq!(little.into_inner().bit_offset(), offset);
+            }
+        }
+    }
+}
@@ -0,0 +1,244 @@
+use crate::bit_source::BitSource;
+use crate::decoder::{count_leading_zeroes, read_unsigned, unsigned_to_signed};
+use core::ops::Deref;
+
+/// An Exponential-Golomb parser over a sequence of non-contiguous byte chunks.
+///
+/// The chunks are read as if they were concatenated, so a codeword may span any number of chunk
+/// boundaries. Any type that dereferences to `[u8]` can be used as a chunk, e.g. `&[u8]`,
+/// `Vec<u8>` or `std::io::IoSlice`.
+pub struct ExpGolombChunkedDecoder<'a, C> {
+    iter: ChunkedBitIterator<'a, C>,
+}
+
+impl<'a, C: Deref<Target = [u8]>> ExpGolombChunkedDecoder<'a, C> {
+    /// Create a new `ExpGolombChunkedDecoder`.
+    ///
+    /// `start` denotes the starting position in the first byte of the first non-empty chunk and
+    /// goes from 0 (first) to 7 (last). This function returns `None` if all the chunks are empty
+    /// or if `start` is not within \[0, 7\].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombChunkedDecoder;
+    /// // 00000000111111111 - 510, split across three chunks
+    /// let first: &[u8] = &[0b00000000];
+    /// let second: &[u8] = &[0b11111111];
+    /// let third: &[u8] = &[0b10000000];
+    /// let chunks = [first, second, third];
+    ///
+    /// let mut reader = ExpGolombChunkedDecoder::new(&chunks, 0).unwrap();
+    /// assert_eq!(reader.next_unsigned(), Some(510));
+    /// ```
+    ///
+    /// Reading from the buffers of a vectored read:
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombChunkedDecoder;
+    /// use std::io::IoSlice;
+    ///
+    /// let chunks = [IoSlice::new(&[0b01000110]), IoSlice::new(&[0b10000000])];
+    /// let mut reader = ExpGolombChunkedDecoder::new(&chunks, 0).unwrap();
+    /// assert_eq!(reader.next_unsigned(), Some(1));
+    /// assert_eq!(reader.next_unsigned(), Some(5));
+    /// assert_eq!(reader.next_unsigned(), Some(0));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new(chunks: &'a [C], start: u32) -> Option<ExpGolombChunkedDecoder<'a, C>> {
+        if start > 7 {
+            return None;
+        }
+        let chunk = chunks.iter().position(|c| !c.is_empty())?;
+        Some(ExpGolombChunkedDecoder {
+            iter: ChunkedBitIterator {
+                chunks,
+                chunk,
+                index: 0,
+                bit_pos: start,
+            },
+        })
+    }
+
+    /// Read the next bit (i.e, as a flag). Returns `None` if the end of the last chunk is reached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombChunkedDecoder;
+    /// let first: &[u8] = &[0b00000001];
+    /// let second: &[u8] = &[0b10000000];
+    /// let chunks = [first, second];
+    ///
+    /// let mut reader = ExpGolombChunkedDecoder::new(&chunks, 7).unwrap();
+    /// assert_eq!(reader.next_bit(), Some(1));
+    /// assert_eq!(reader.next_bit(), Some(1));
+    /// assert_eq!(reader.next_bit(), Some(0));
+    /// ```
+    #[inline]
+    pub fn next_bit(&mut self) -> Option<u8> {
+        self.iter.next()
+    }
+
+    /// Read the next Exp-Golomb value as an unsigned integer. Returns `None` if the end of the
+    /// last chunk is reached before parsing is completed or if the coded value is exceeds the
+    /// limits of a `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombChunkedDecoder;
+    /// // 00110 - 5, split after the third bit
+    /// let first: &[u8] = &[0b00000001];
+    /// let second: &[u8] = &[0b10000000];
+    /// let chunks = [first, second];
+    ///
+    /// let mut reader = ExpGolombChunkedDecoder::new(&chunks, 5).unwrap();
+    /// assert_eq!(reader.next_unsigned(), Some(5));
+    /// assert_eq!(reader.next_unsigned(), None);
+    /// ```
+    #[inline]
+    #[must_use = "use `ExpGolombChunkedDecoder::skip_next` if the value is not needed"]
+    pub fn next_unsigned(&mut self) -> Option<u64> {
+        read_unsigned(&mut self.iter)
+    }
+
+    /// Read the next Exp-Golomb value, interpreting it as a signed integer. Returns `None` if the
+    /// end of the last chunk is reached before parsing is completed or if the coded value is
+    /// exceeds the limits of a `i64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombChunkedDecoder;
+    /// // 00101 - -2
+    /// let first: &[u8] = &[0b00];
+    /// let second: &[u8] = &[0b10100000];
+    /// let chunks = [first, second];
+    ///
+    /// let mut reader = ExpGolombChunkedDecoder::new(&chunks, 6).unwrap();
+    /// assert_eq!(reader.next_signed(), Some(-2));
+    /// ```
+    #[inline]
+    #[must_use = "use `ExpGolombChunkedDecoder::skip_next` if the value is not needed"]
+    pub fn next_signed(&mut self) -> Option<i64> {
+        self.next_unsigned().map(unsigned_to_signed)
+    }
+
+    /// Skip the next Exp-Golomb encoded value. Any parsing error at the end of the last chunk is
+    /// ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombChunkedDecoder;
+    /// let first: &[u8] = &[0b01001001];
+    /// let second: &[u8] = &[0b00110000];
+    /// let chunks = [first, second];
+    ///
+    /// let mut reader = ExpGolombChunkedDecoder::new(&chunks, 0).unwrap();
+    /// reader.skip_next();
+    /// reader.skip_next();
+    /// reader.skip_next();
+    /// assert_eq!(reader.next_unsigned(), Some(2));
+    /// ```
+    #[inline]
+    pub fn skip_next(&mut self) {
+        if let Some(lz) = count_leading_zeroes(&mut self.iter, u64::BITS) {
+            for _ in 0..lz {
+                if self.iter.next().is_none() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+struct ChunkedBitIterator<'a, C> {
+    chunks: &'a [C],
+    chunk: usize,
+    index: usize,
+    bit_pos: u32,
+}
+
+impl<'a, C: Deref<Target = [u8]>> core::iter::Iterator for ChunkedBitIterator<'a, C> {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let buf = self.chunks.get(self.chunk)?;
+        let curr_byte = buf[self.index];
+        let shift = 7 - self.bit_pos;
+        let bit = curr_byte & (1 << shift);
+
+        self.bit_pos += 1;
+        if self.bit_pos == 8 {
+            self.bit_pos = 0;
+            self.index += 1;
+            if self.index == buf.len() {
+                // Move on to the next non-empty chunk so that `index` is always valid while
+                // `chunk` is in bounds
+                self.index = 0;
+                self.chunk += 1;
+                while self.chunks.get(self.chunk).is_some_and(|c| c.is_empty()) {
+                    self.chunk += 1;
+                }
+            }
+        }
+
+        Some(bit >> shift)
+    }
+}
+
+impl<'a, C: Deref<Target = [u8]>> BitSource for ChunkedBitIterator<'a, C> {
+    #[inline]
+    fn next_bit(&mut self) -> Option<u8> {
+        self.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+
+    #[test]
+    fn all_chunks_empty() {
+        let chunks: [&[u8]; 0] = [];
+        assert!(ExpGolombChunkedDecoder::new(&chunks, 0).is_none());
+        let chunks: [&[u8]; 2] = [&[], &[]];
+        assert!(ExpGolombChunkedDecoder::new(&chunks, 0).is_none());
+    }
+
+    #[test]
+    fn start_bit_validity() {
+        let chunks: [&[u8]; 1] = [&[0b01000000]];
+        for i in 0..=7 {
+            assert!(ExpGolombChunkedDecoder::new(&chunks, i).is_some());
+        }
+        assert!(ExpGolombChunkedDecoder::new(&chunks, 8).is_none());
+    }
+
+    #[test]
+    fn empty_chunks_are_skipped() {
+        // 00000000111111111 - 510
+        let chunks: [&[u8]; 5] = [&[], &[0b00000000], &[], &[0b11111111], &[0b10000000]];
+        let mut reader = ExpGolombChunkedDecoder::new(&chunks, 0).unwrap();
+        assert_eq!(reader.next_unsigned(), Some(510));
+        for _ in 0..7 {
+            assert_eq!(reader.next_bit(), Some(0));
+        }
+        assert_eq!(reader.next_bit(), None);
+        assert_eq!(reader.next_bit(), None);
+    }
+
+    #[test]
+    fn matches_contiguous_decoder() {
+        test_util::for_each_split(|first, second, start| {
+            let chunks = [&[], first, &[], second, &[]];
+            let mut chunked = ExpGolombChunkedDecoder::new(&chunks, start).unwrap();
+            test_util::assert_matches_contiguous(start, || chunked.next_unsigned());
+        });
+    }
+}
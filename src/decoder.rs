@@ -1,14 +1,56 @@
+use crate::bit_source::BitSource;
+use crate::error::{DecodeError, DecodeErrorKind, NewError};
+use core::num::NonZeroU64;
+use core::ops::Range;
+
+/// A bit position in a buffer as the index of a byte and the offset of a bit within it, from 0
+/// (most significant) to 7 (least significant).
+///
+/// The layout is `#[repr(C)]` so that positions can be passed through an FFI boundary.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BitPosition {
+    /// The index of the byte in the buffer.
+    pub byte_index: usize,
+    /// The offset of the bit within the byte.
+    pub bit_offset: u8,
+}
+
+impl BitPosition {
+    /// Create a new `BitPosition`.
+    #[inline]
+    pub const fn new(byte_index: usize, bit_offset: u8) -> BitPosition {
+        BitPosition {
+            byte_index,
+            bit_offset,
+        }
+    }
+}
+
+/// The coding of a field read by [`ExpGolombDecoder::decode_fields`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FieldSpec {
+    /// An unsigned Exp-Golomb value, i.e. a `ue(v)` field.
+    Unsigned,
+    /// A signed Exp-Golomb value, i.e. a `se(v)` field.
+    Signed,
+    /// An unsigned integer of a fixed number of bits, i.e. a `u(n)` field.
+    Fixed(u32),
+}
+
 /// An Exponential-Golomb parser.
 pub struct ExpGolombDecoder<'a> {
     iter: BitIterator<'a>,
+    max_leading_zeros: u32,
 }
 
 impl<'a> ExpGolombDecoder<'a> {
     /// Create a new `ExpGolombDecoder`.
     ///
     /// `start` denotes the starting position in the first byte of `buf` and goes from 0 (first) to
-    ///  7 (last). This function returns `None` if the buffer is empty or if `start` is  not within
-    /// \[0, 7\].
+    /// 7 (last). This function returns `None` if the buffer is empty or if `start` is not within
+    /// \[0, 7\]. Use [`ExpGolombDecoder::new_at_bit`] to start at an offset counted from the
+    /// beginning of the whole buffer instead.
     ///
     /// # Examples
     ///
@@ -31,14 +73,282 @@ impl<'a> ExpGolombDecoder<'a> {
     #[inline]
     #[must_use]
     pub fn new(buf: &'a [u8], start: u32) -> Option<ExpGolombDecoder<'a>> {
-        if buf.is_empty() || start > 7 {
+        ExpGolombDecoder::try_new(buf, start).ok()
+    }
+
+    /// Create a new `ExpGolombDecoder`, returning a [`NewError`] that says which argument is
+    /// invalid instead of `None`. See [`ExpGolombDecoder::new`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::{ExpGolombDecoder, NewError};
+    /// let data = [0b01000000];
+    /// assert!(ExpGolombDecoder::try_new(&data, 7).is_ok());
+    /// assert_eq!(ExpGolombDecoder::try_new(&[], 0).err(), Some(NewError::EmptyBuffer));
+    ///
+    /// let err = ExpGolombDecoder::try_new(&data, 8).err().unwrap();
+    /// assert_eq!(err, NewError::InvalidStart(8));
+    /// assert_eq!(err.to_string(), "start bit 8 is not within the first byte");
+    /// ```
+    #[inline]
+    pub fn try_new(buf: &'a [u8], start: u32) -> Result<ExpGolombDecoder<'a>, NewError> {
+        if buf.is_empty() {
+            return Err(NewError::EmptyBuffer);
+        }
+        if start > 7 {
+            return Err(NewError::InvalidStart(start));
+        }
+        // `start` counts from the first byte, not from the beginning of the whole buffer
+        debug_assert!(
+            (start as usize) < buf.len() * 8,
+            "`start` must be within the first byte, use `new_at_bit` for buffer offsets"
+        );
+        Ok(ExpGolombDecoder {
+            iter: BitIterator::new(buf, start),
+            max_leading_zeros: u64::BITS,
+        })
+    }
+
+    /// Create a new `ExpGolombDecoder` with a `start` that is fixed at compile time. This is the
+    /// same as [`ExpGolombDecoder::new`] except that a `START` outside of \[0, 7\] fails to compile,
+    /// so `None` is only returned if the buffer is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// let data = [0b00100000];
+    /// let mut reader = ExpGolombDecoder::new_const::<1>(&data).unwrap();
+    /// assert_eq!(reader.next_unsigned(), Some(1));
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// let reader = ExpGolombDecoder::new_const::<8>(&[0]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new_const<const START: u32>(buf: &'a [u8]) -> Option<ExpGolombDecoder<'a>> {
+        const { assert!(START <= 7, "`START` must be within [0, 7]") };
+        if buf.is_empty() {
+            return None;
+        }
+        Some(ExpGolombDecoder {
+            iter: BitIterator::new(buf, START),
+            max_leading_zeros: u64::BITS,
+        })
+    }
+
+    /// Create a new `ExpGolombDecoder` that starts at `pos`, e.g. to resume decoding from a
+    /// position saved with [`ExpGolombDecoder::position`].
+    ///
+    /// This function returns `None` if `pos.bit_offset` is not within \[0, 7\] or if `pos` is past
+    /// the end of `buf`. A position of `(buf.len(), 0)` is accepted and gives a decoder that is
+    /// already at the end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::{BitPosition, ExpGolombDecoder};
+    /// // 010 - 1, 00110 - 5, 011 - 2
+    /// let data = [0b01000110, 0b01100000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.next_unsigned(), Some(1));
+    /// let pos = reader.position();
+    ///
+    /// let mut resumed = ExpGolombDecoder::from_position(&data, pos).unwrap();
+    /// assert_eq!(resumed.next_unsigned(), Some(5));
+    /// assert_eq!(resumed.next_unsigned(), Some(2));
+    ///
+    /// assert!(ExpGolombDecoder::from_position(&data, BitPosition::new(2, 0)).is_some());
+    /// assert!(ExpGolombDecoder::from_position(&data, BitPosition::new(2, 1)).is_none());
+    /// assert!(ExpGolombDecoder::from_position(&data, BitPosition::new(0, 8)).is_none());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn from_position(buf: &'a [u8], pos: BitPosition) -> Option<ExpGolombDecoder<'a>> {
+        let bit_pos = pos.bit_offset as u32;
+        if bit_pos > 7 || (pos.byte_index, bit_pos) > (buf.len(), 0) {
             return None;
         }
+        let mut iter = BitIterator::new(buf, bit_pos);
+        iter.index = pos.byte_index;
+        iter.start_index = pos.byte_index;
         Some(ExpGolombDecoder {
+            iter,
+            max_leading_zeros: u64::BITS,
+        })
+    }
+
+    /// Create a new `ExpGolombDecoder` starting `bit_offset` bits from the most significant bit of
+    /// `buf[0]`, which may be past the first byte unlike the `start` of
+    /// [`ExpGolombDecoder::new`]. Returns `None` if `bit_offset` is past the end of `buf`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 011 - 2, starting at bit 10
+    /// let data = [0b11111111, 0b11011000];
+    /// let mut reader = ExpGolombDecoder::new_at_bit(&data, 10).unwrap();
+    /// assert_eq!(reader.next_unsigned(), Some(2));
+    ///
+    /// assert!(ExpGolombDecoder::new_at_bit(&data, 16).is_some());
+    /// assert!(ExpGolombDecoder::new_at_bit(&data, 17).is_none());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new_at_bit(buf: &'a [u8], bit_offset: usize) -> Option<ExpGolombDecoder<'a>> {
+        let pos = BitPosition::new(bit_offset / 8, (bit_offset % 8) as u8);
+        ExpGolombDecoder::from_position(buf, pos)
+    }
+
+    /// Create a new `ExpGolombDecoder` starting `bit_offset` bits from the most significant bit of
+    /// `buf[0]`. This is the same as [`ExpGolombDecoder::new_at_bit`] except that the offset is a
+    /// `u64`, so offsets saved on a 64-bit platform can be used on a 32-bit one. Returns `None` if
+    /// `bit_offset` is past the end of `buf`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 011 - 2, starting at bit 10
+    /// let data = [0b11111111, 0b11011000];
+    /// let mut reader = ExpGolombDecoder::new_at(&data, 10).unwrap();
+    /// assert_eq!(reader.next_unsigned(), Some(2));
+    ///
+    /// assert!(ExpGolombDecoder::new_at(&data, 16).is_some());
+    /// assert!(ExpGolombDecoder::new_at(&data, u64::MAX).is_none());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new_at(buf: &'a [u8], bit_offset: u64) -> Option<ExpGolombDecoder<'a>> {
+        if bit_offset > (buf.len() as u64).saturating_mul(8) {
+            return None;
+        }
+        let pos = BitPosition::new((bit_offset / 8) as usize, (bit_offset % 8) as u8);
+        ExpGolombDecoder::from_position(buf, pos)
+    }
+
+    /// Create a new `ExpGolombDecoder` without checking that `buf` is non-empty and that `start` is
+    /// within \[0, 7\]. Unlike [`ExpGolombDecoder::new`], this can be used in `const` contexts.
+    ///
+    /// Passing arguments that [`ExpGolombDecoder::new`] would reject does not cause undefined
+    /// behavior, but the decoder may then panic or return meaningless values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// const DATA: [u8; 1] = [0b01000000];
+    /// const READER: ExpGolombDecoder<'static> = ExpGolombDecoder::new_unchecked(&DATA, 0);
+    ///
+    /// let mut reader = READER;
+    /// assert_eq!(reader.next_unsigned(), Some(1));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn new_unchecked(buf: &'a [u8], start: u32) -> ExpGolombDecoder<'a> {
+        debug_assert!(!buf.is_empty() && start <= 7);
+        ExpGolombDecoder {
             iter: BitIterator::new(buf, start),
+            max_leading_zeros: u64::BITS,
+        }
+    }
+
+    /// Create a new `ExpGolombDecoder` that treats `end_bit` as the end of the bitstream, e.g. to
+    /// exclude the RBSP trailing bits. `end_bit` is counted from the most significant bit of
+    /// `buf[0]`.
+    ///
+    /// This function returns `None` under the same conditions as [`ExpGolombDecoder::new`] or if
+    /// `end_bit` is before `start` or past the end of `buf`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 00110 followed by the stop bit and alignment zeros
+    /// let data = [0b00110100];
+    ///
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.next_unsigned(), Some(5));
+    /// assert_eq!(reader.next_unsigned(), Some(0));
+    ///
+    /// let mut reader = ExpGolombDecoder::new_bounded(&data, 0, 5).unwrap();
+    /// assert_eq!(reader.next_unsigned(), Some(5));
+    /// assert_eq!(reader.next_unsigned(), None);
+    /// assert_eq!(reader.next_bit(), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new_bounded(buf: &'a [u8], start: u32, end_bit: usize) -> Option<ExpGolombDecoder<'a>> {
+        let mut iter = Self::new(buf, start)?.iter;
+        let end = (end_bit / 8, (end_bit % 8) as u32);
+        if end < (0, start) || end > (buf.len(), 0) {
+            return None;
+        }
+        (iter.end_index, iter.end_bit_pos) = end;
+        Some(ExpGolombDecoder {
+            iter,
+            max_leading_zeros: u64::BITS,
         })
     }
 
+    /// Limit the codewords accepted by the decoder to at most `max_bits` bits, e.g. to reject
+    /// absurdly long codewords in untrusted data. Reads of a longer codeword return `None` as soon
+    /// as its prefix exceeds the limit, without scanning the rest of the zeros. Since codewords
+    /// have an odd length, an even `max_bits` behaves like `max_bits - 1`, and the one-bit codeword
+    /// of 0 is always accepted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 00110 - 5, 0001001 - 8
+    /// let data = [0b00110000, 0b10010000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap().max_codeword_bits(6);
+    /// assert_eq!(reader.next_unsigned(), Some(5));
+    /// assert_eq!(reader.next_unsigned(), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn max_codeword_bits(mut self, max_bits: u32) -> ExpGolombDecoder<'a> {
+        self.max_leading_zeros = (max_bits.saturating_sub(1) / 2).min(u64::BITS);
+        self
+    }
+
+    /// Register `f` to be called with every bit the decoder consumes from now on, e.g. to mirror
+    /// the decoding into a bit-level trace. This replaces any previous observer. Bits that are
+    /// skipped over, including the rest of the bitstream when a read fails, are passed to `f` as
+    /// well, while moving with [`ExpGolombDecoder::seek`] is not observed.
+    ///
+    /// The observer is borrowed rather than boxed so that this works without allocating. It is
+    /// taken by shared reference so that the decoder stays covariant over `'a`, so an observer
+    /// that records the bits needs interior mutability, e.g. a [`Cell`](core::cell::Cell) or a
+    /// [`RefCell`](core::cell::RefCell).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// use std::cell::RefCell;
+    ///
+    /// let bits = RefCell::new(Vec::new());
+    /// let record = |bit| bits.borrow_mut().push(bit);
+    ///
+    /// let data = [0b01000110];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// reader.set_bit_observer(&record);
+    /// assert_eq!(reader.next_unsigned(), Some(1));
+    /// reader.skip_next();
+    /// assert_eq!(bits.into_inner(), [0, 1, 0, 0, 0, 1, 1, 0]);
+    /// ```
+    #[inline]
+    pub fn set_bit_observer(&mut self, f: &'a dyn Fn(u8)) {
+        self.iter.observer = Some(f);
+    }
+
     /// Read the next bit (i.e, as a flag). Returns `None` if the end of the bitstream is reached.
     ///
     /// # Examples
@@ -61,20 +371,56 @@ impl<'a> ExpGolombDecoder<'a> {
         self.iter.next()
     }
 
+    /// Read the next `num_bits` bits as an unsigned integer, most significant bit first (i.e, a
+    /// `u(n)` field). Returns `None` if `num_bits` is more than 64 or if the end of the bitstream is
+    /// reached before all the bits are read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// let data = [0b00001011, 0b01000000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 4).unwrap();
+    /// assert_eq!(reader.next_n_bits(6), Some(0b101101));
+    /// assert_eq!(reader.next_n_bits(0), Some(0));
+    /// assert_eq!(reader.next_n_bits(7), None);
+    /// ```
     #[inline]
-    fn count_leading_zeroes(&mut self) -> Option<u32> {
-        let mut leading_zeros = 0;
-        for bit in self.iter.by_ref() {
-            if bit == 0 {
-                leading_zeros += 1;
-                if leading_zeros > u64::BITS {
-                    return None;
-                }
-            } else {
-                return Some(leading_zeros);
-            }
+    pub fn next_n_bits(&mut self, num_bits: u32) -> Option<u64> {
+        if num_bits > u64::BITS || self.iter.offset_position(num_bits as usize).is_none() {
+            self.iter.move_to_end();
+            return None;
         }
-        None
+        let mut value = 0;
+        for _ in 0..num_bits {
+            value = (value << 1) | self.iter.next()? as u64;
+        }
+        Some(value)
+    }
+
+    /// Read the next `num_bits` bits as a two's complement signed integer (i.e, an `i(n)` field).
+    /// This is unrelated to the Exp-Golomb mapping used by [`ExpGolombDecoder::next_signed`].
+    /// Returns `None` if `num_bits` is not within \[1, 64\] or if the end of the bitstream is
+    /// reached before all the bits are read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// let data = [0b11100101];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.next_signed_fixed(3), Some(-1));
+    /// assert_eq!(reader.next_signed_fixed(5), Some(5));
+    /// ```
+    #[inline]
+    pub fn next_signed_fixed(&mut self, num_bits: u32) -> Option<i64> {
+        if num_bits == 0 {
+            self.iter.move_to_end();
+            return None;
+        }
+        let shift = u64::BITS - num_bits.min(u64::BITS);
+        self.next_n_bits(num_bits)
+            .map(|value| ((value << shift) as i64) >> shift)
     }
 
     /// Read the next Exp-Golomb value as an unsigned integer. Returns `None` if the end of the
@@ -106,10 +452,11 @@ impl<'a> ExpGolombDecoder<'a> {
     ///
     /// ```
     /// # use exp_golomb::ExpGolombDecoder;
+    /// // 64 leading zeros followed by `1` and 64 zeros
     /// let data = [
     ///     0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000,
-    ///     0b00000000, 0b00000001, 0b11111111, 0b11111111, 0b11111111, 0b11111111, 0b11111111,
-    ///     0b11111111, 0b11111111, 0b11111111,
+    ///     0b00000000, 0b00000001, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000,
+    ///     0b00000000, 0b00000000, 0b00000000,
     /// ];
     /// let mut reader = ExpGolombDecoder::new(&data, 7).unwrap();
     /// assert_eq!(reader.next_unsigned(), Some(u64::MAX));
@@ -117,159 +464,2103 @@ impl<'a> ExpGolombDecoder<'a> {
     /// // Attempt to parse a 65-bit number
     /// let mut reader = ExpGolombDecoder::new(&data, 6).unwrap();
     /// assert_eq!(reader.next_unsigned(), None);
+    ///
+    /// // Any other suffix after 64 leading zeros is also too large
+    /// let mut data = data;
+    /// data[16] = 0b00000001;
+    /// let mut reader = ExpGolombDecoder::new(&data, 7).unwrap();
+    /// assert_eq!(reader.next_unsigned(), None);
     /// ```
     #[inline]
     #[must_use = "use `ExpGolombReader::skip_next` if the value is not needed"]
     pub fn next_unsigned(&mut self) -> Option<u64> {
-        let mut lz = self.count_leading_zeroes()?;
-        let x = 1u64.wrapping_shl(lz) - 1;
-        let mut y = 0;
-
-        if lz != 0 {
-            for bit in self.iter.by_ref() {
-                y <<= 1;
-                y |= bit as u64;
-                lz -= 1;
-                if lz == 0 {
-                    break;
-                }
-            }
-            if lz != 0 {
-                return None;
-            }
-        }
-        Some(x + y)
+        self.next_unsigned_with_prefix().map(|(value, _)| value)
     }
 
-    /// Read the next Exp-Golomb value, interpreting it as a signed integer. Returns `None` if the
-    /// end of the bitstream is reached before parsing is completed or if the coded value is
-    /// exceeds the limits of a `i64`.
+    /// Read the next Exp-Golomb value as an unsigned integer along with the number of leading
+    /// zeros in its codeword. The codeword is `2 * leading_zeros + 1` bits long. Returns `None`
+    /// under the same conditions as [`ExpGolombDecoder::next_unsigned`].
     ///
     /// # Examples
     ///
     /// ```
     /// # use exp_golomb::ExpGolombDecoder;
-    /// // Concatenated Wikipedia example:
-    /// // https://en.wikipedia.org/wiki/Exponential-Golomb_coding#Extension_to_negative_numbers
-    /// let data = [0b10100110, 0b01000010, 0b10011000, 0b11100010, 0b00000100, 0b10000000];
+    /// // 1                 - 0
+    /// // 00110             - 5
+    /// // 00000000111111111 - 510
+    /// let data = [0b10011000, 0b00000011, 0b11111110];
     ///
     /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
-    /// assert_eq!(reader.next_signed(), Some(0));
-    /// assert_eq!(reader.next_signed(), Some(1));
-    /// assert_eq!(reader.next_signed(), Some(-1));
-    /// assert_eq!(reader.next_signed(), Some(2));
-    /// assert_eq!(reader.next_signed(), Some(-2));
-    /// assert_eq!(reader.next_signed(), Some(3));
-    /// assert_eq!(reader.next_signed(), Some(-3));
-    /// assert_eq!(reader.next_signed(), Some(4));
-    /// assert_eq!(reader.next_signed(), Some(-4));
-    /// assert_eq!(reader.next_signed(), None);
-    /// assert_eq!(reader.next_signed(), None);
+    /// assert_eq!(reader.next_unsigned_with_prefix(), Some((0, 0)));
+    /// assert_eq!(reader.next_unsigned_with_prefix(), Some((5, 2)));
+    /// assert_eq!(reader.next_unsigned_with_prefix(), Some((510, 8)));
+    /// assert_eq!(reader.next_unsigned_with_prefix(), None);
     /// ```
+    #[inline]
+    #[must_use = "use `ExpGolombReader::skip_next` if the value is not needed"]
+    pub fn next_unsigned_with_prefix(&mut self) -> Option<(u64, u32)> {
+        self.try_next_unsigned_with_prefix().ok()
+    }
+
+    /// Fast path for codewords that end within the current byte, e.g. runs of small values.
+    /// Returns the value and the number of leading zeros, or `None` without consuming anything if
+    /// the codeword does not end within the current byte.
+    #[inline]
+    fn next_unsigned_within_byte(&mut self) -> Option<(u8, u32)> {
+        let (bits, available) = self.iter.current_bits()?;
+        // A `1` is the whole codeword of 0, the most common value in many bitstreams
+        if bits & 0x80 != 0 {
+            self.iter.advance_within_byte(1);
+            return Some((0, 0));
+        }
+        let (value, len) = SMALL_VALUES[bits as usize];
+        if len != 0 && len as u32 <= available && len as u32 / 2 <= self.max_leading_zeros {
+            self.iter.advance_within_byte(len as u32);
+            return Some((value, len as u32 / 2));
+        }
+        None
+    }
+
+    #[inline]
+    fn try_next_unsigned_with_prefix(&mut self) -> Result<(u64, u32), DecodeErrorKind> {
+        if let Some((value, lz)) = self.next_unsigned_within_byte() {
+            return Ok((value as u64, lz));
+        }
+        let result = try_read_unsigned_with_prefix(&mut self.iter, self.max_leading_zeros);
+        if result.is_err() {
+            self.iter.move_to_end();
+        }
+        result
+    }
+
+    /// Read the next Exp-Golomb value as an unsigned integer if its codeword fits within
+    /// `remaining_bits`, which is then decreased by the codeword length. This is useful for
+    /// decoding a group of values whose total size in bits is known but whose count is not.
     ///
-    /// The coded value is limited to 64 bits. Trying to parse larger values would return
-    /// `None`.
+    /// Returns `None` without consuming anything if the codeword is longer than `remaining_bits`.
+    /// Otherwise `None` is returned under the same conditions as
+    /// [`ExpGolombDecoder::next_unsigned`].
+    ///
+    /// # Examples
     ///
     /// ```
     /// # use exp_golomb::ExpGolombDecoder;
-    /// let data = [
-    ///     0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000,
-    ///     0b00000000, 0b00000001, 0b11111111, 0b11111111, 0b11111111, 0b11111111, 0b11111111,
-    ///     0b11111111, 0b11111111, 0b11111111,
-    /// ];
-    /// let mut reader = ExpGolombDecoder::new(&data, 7).unwrap();
-    /// assert_eq!(reader.next_signed(), Some(i64::MIN));
+    /// // 010 - 1, 00110 - 5
+    /// let data = [0b01000110];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
     ///
-    /// // Attempt to parse a 65-bit number
-    /// let mut reader = ExpGolombDecoder::new(&data, 6).unwrap();
-    /// assert_eq!(reader.next_signed(), None);
+    /// let mut remaining_bits = 7;
+    /// assert_eq!(reader.next_unsigned_within(&mut remaining_bits), Some(1));
+    /// assert_eq!(remaining_bits, 4);
+    /// assert_eq!(reader.next_unsigned_within(&mut remaining_bits), None);
+    /// assert_eq!(remaining_bits, 4);
+    ///
+    /// // The codeword was not consumed
+    /// assert_eq!(reader.next_unsigned(), Some(5));
     /// ```
     #[inline]
     #[must_use = "use `ExpGolombReader::skip_next` if the value is not needed"]
-    pub fn next_signed(&mut self) -> Option<i64> {
-        self.next_unsigned().map(|k| {
-            let factor = if k % 2 == 0 { -1 } else { 1 };
-            factor * (k / 2 + k % 2) as i64
-        })
+    pub fn next_unsigned_within(&mut self, remaining_bits: &mut usize) -> Option<u64> {
+        let mut peek = self.iter.detached();
+        let Some(lz) = count_leading_zeroes(&mut peek, self.max_leading_zeros) else {
+            self.iter.move_to_end();
+            return None;
+        };
+        let len = 2 * lz as usize + 1;
+        if len > *remaining_bits {
+            return None;
+        }
+        let value = self.next_unsigned()?;
+        *remaining_bits -= len;
+        Some(value)
     }
 
-    /// Skip the next Exp-Golomb encoded value. Any parsing error at the end of the bitstream is
-    /// ignored.
+    /// Read the next Exp-Golomb value as an unsigned integer, checking that its codeword is
+    /// exactly `expected_bits` long, e.g. to compare against a reference trace of field sizes.
+    ///
+    /// Returns `None` without consuming anything if the codeword has a different length.
+    /// Otherwise `None` is returned under the same conditions as
+    /// [`ExpGolombDecoder::next_unsigned`].
     ///
     /// # Examples
     ///
     /// ```
     /// # use exp_golomb::ExpGolombDecoder;
-    /// let data = [0b01001001, 0b00110000];
+    /// // 010 - 1, 00110 - 5
+    /// let data = [0b01000110];
     /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
-    /// reader.skip_next();
-    /// reader.skip_next();
-    /// reader.skip_next();
-    /// assert_eq!(reader.next_unsigned(), Some(2));
-    /// reader.skip_next();
-    /// assert_eq!(reader.next_unsigned(), None);
-    /// reader.skip_next();
-    /// assert_eq!(reader.next_unsigned(), None);
+    /// assert_eq!(reader.next_unsigned_expect_len(3), Some(1));
+    /// assert_eq!(reader.next_unsigned_expect_len(3), None);
+    /// assert_eq!(reader.next_unsigned_expect_len(5), Some(5));
     /// ```
     #[inline]
-    pub fn skip_next(&mut self) {
-        if let Some(lz) = self.count_leading_zeroes() {
-            self.iter.skip_bits(lz);
+    #[must_use = "use `ExpGolombReader::skip_next` if the value is not needed"]
+    pub fn next_unsigned_expect_len(&mut self, expected_bits: u32) -> Option<u64> {
+        let mut peek = self.iter.detached();
+        let Some(lz) = count_leading_zeroes(&mut peek, self.max_leading_zeros) else {
+            self.iter.move_to_end();
+            return None;
+        };
+        if 2 * lz + 1 != expected_bits {
+            return None;
         }
+        self.next_unsigned()
     }
-}
-
-struct BitIterator<'a> {
-    buf: &'a [u8],
-    index: usize,
-    bit_pos: u32,
-}
 
-impl<'a> BitIterator<'a> {
+    /// Read an unsigned integer written with an escape for large values by
+    /// [`ExpGolombEncoder::put_unsigned_escaped`](crate::ExpGolombEncoder::put_unsigned_escaped)
+    /// with the same `threshold` and `escape_bits`. Returns `None` if the end of the bitstream is
+    /// reached, if `escape_bits` is more than 64, if a regular codeword holds a value of at least
+    /// `threshold` since the encoder would have escaped it, or if an escaped value does not fit in
+    /// a `u64`. The decoder is moved to the end of the bitstream in every case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 011 - 2, 00 - escape, 1111100101 - 997
+    /// let data = [0b01100111, 0b11001010];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.next_unsigned_escaped(3, 10), Some(2));
+    /// assert_eq!(reader.next_unsigned_escaped(3, 10), Some(1000));
+    /// ```
+    #[inline]
+    #[must_use = "use `ExpGolombReader::skip_next` if the value is not needed"]
+    pub fn next_unsigned_escaped(&mut self, threshold: u64, escape_bits: u32) -> Option<u64> {
+        let max_leading_zeros = threshold.checked_ilog2().unwrap_or(0);
+        let value = match try_count_leading_zeroes(&mut self.iter, max_leading_zeros) {
+            Ok(lz) => {
+                let x = 1u64.checked_shl(lz).map_or(u64::MAX, |v| v - 1);
+                self.next_n_bits(lz)
+                    .and_then(|bits| x.checked_add(bits))
+                    .filter(|&value| value < threshold)
+            }
+            // One more leading zero than any value below the threshold
+            Err(DecodeErrorKind::Overflow) => self
+                .next_n_bits(escape_bits)
+                .and_then(|bits| threshold.checked_add(bits)),
+            Err(DecodeErrorKind::EndOfStream) => None,
+        };
+        if value.is_none() {
+            self.iter.move_to_end();
+        }
+        value
+    }
+
+    /// Read the next Exp-Golomb value as an unsigned integer and add one to it, e.g. for the
+    /// `_minus1` syntax elements of H.264 and H.265. Returns `None` under the same conditions as
+    /// [`ExpGolombDecoder::next_unsigned`] or if the coded value is `u64::MAX`, in which case the
+    /// codeword is still consumed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// use std::num::NonZeroU64;
+    ///
+    /// // 1 - 0, 00110 - 5
+    /// let data = [0b10011000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.next_unsigned_plus_one(), NonZeroU64::new(1));
+    /// assert_eq!(reader.next_unsigned_plus_one(), NonZeroU64::new(6));
+    /// ```
+    #[inline]
+    #[must_use = "use `ExpGolombReader::skip_next` if the value is not needed"]
+    pub fn next_unsigned_plus_one(&mut self) -> Option<NonZeroU64> {
+        self.next_unsigned_map(|value| NonZeroU64::new(value.checked_add(1)?))
+    }
+
+    /// Read the length of a run of zeros written by
+    /// [`ExpGolombEncoder::put_zero_run`](crate::ExpGolombEncoder::put_zero_run). Returns `None`
+    /// under the same conditions as [`ExpGolombDecoder::next_unsigned`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 00110 - 5
+    /// let data = [0b00110000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.next_zero_run(), Some(5));
+    /// ```
+    #[inline]
+    #[must_use = "use `ExpGolombReader::skip_next` if the value is not needed"]
+    pub fn next_zero_run(&mut self) -> Option<u64> {
+        self.next_unsigned()
+    }
+
+    /// Read the next Exp-Golomb value as an unsigned integer and pass it through `f`, e.g. to
+    /// convert it to an index or an enum. Returns `None` if decoding fails or if `f` returns
+    /// `None`, in which case the codeword is still consumed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// #[derive(Debug, PartialEq)]
+    /// enum SliceType {
+    ///     P,
+    ///     B,
+    ///     I,
+    /// }
+    ///
+    /// fn slice_type(value: u64) -> Option<SliceType> {
+    ///     match value {
+    ///         0 => Some(SliceType::P),
+    ///         1 => Some(SliceType::B),
+    ///         2 => Some(SliceType::I),
+    ///         _ => None,
+    ///     }
+    /// }
+    ///
+    /// // 011 - 2, 00100 - 3
+    /// let data = [0b01100100];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.next_unsigned_map(slice_type), Some(SliceType::I));
+    /// assert_eq!(reader.next_unsigned_map(slice_type), None);
+    /// ```
+    #[inline]
+    pub fn next_unsigned_map<T>(&mut self, f: impl FnOnce(u64) -> Option<T>) -> Option<T> {
+        self.next_unsigned().and_then(f)
+    }
+
+    /// Read the next Exp-Golomb value as an unsigned integer and convert it to `T`, e.g. a `u16`
+    /// or a `usize`. Returns `None` under the same conditions as
+    /// [`ExpGolombDecoder::next_unsigned`] or if the value does not fit in `T`, in which case the
+    /// codeword is still consumed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 00110 - 5, 00000000111111111 - 510
+    /// let data = [0b00110000, 0b00000111, 0b11111100];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// let width: u16 = reader.next_unsigned_as().unwrap();
+    /// assert_eq!(width, 5);
+    /// assert_eq!(reader.next_unsigned_as::<u8>(), None);
+    /// ```
+    #[inline]
+    #[must_use = "use `ExpGolombReader::skip_next` if the value is not needed"]
+    pub fn next_unsigned_as<T: TryFrom<u64>>(&mut self) -> Option<T> {
+        self.next_unsigned_map(|value| T::try_from(value).ok())
+    }
+
+    /// Read the next Exp-Golomb value as an unsigned integer, returning `None` if it is larger
+    /// than `max`, e.g. a limit from a specification that only a corrupted bitstream would exceed.
+    /// The codeword is consumed either way so that decoding can continue after it. Otherwise
+    /// `None` is returned under the same conditions as [`ExpGolombDecoder::next_unsigned`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 011 - 2, 00110 - 5, 1 - 0
+    /// let data = [0b01100110, 0b10000000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.next_unsigned_max(4), Some(2));
+    /// assert_eq!(reader.next_unsigned_max(4), None);
+    /// assert_eq!(reader.next_unsigned_max(4), Some(0));
+    /// ```
+    #[inline]
+    #[must_use = "use `ExpGolombReader::skip_next` if the value is not needed"]
+    pub fn next_unsigned_max(&mut self, max: u64) -> Option<u64> {
+        self.next_unsigned_map(|value| (value <= max).then_some(value))
+    }
+
+    /// Read the next Exp-Golomb value as an unsigned integer along with its codeword, e.g. to copy
+    /// the codeword verbatim to another bitstream. Returns `(value, bits, len)` where `bits` holds
+    /// the codeword right-aligned and `len` is its length in bits. Since the leading zeros are
+    /// implied by `len`, `bits` is always `value + 1`. Returns `None` under the same conditions as
+    /// [`ExpGolombDecoder::next_unsigned`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 1 - 0, 00110 - 5
+    /// let data = [0b10011000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.next_unsigned_raw(), Some((0, 0b1, 1)));
+    /// assert_eq!(reader.next_unsigned_raw(), Some((5, 0b00110, 5)));
+    /// assert_eq!(reader.next_unsigned_raw(), None);
+    /// ```
+    #[inline]
+    #[must_use = "use `ExpGolombReader::skip_next` if the value is not needed"]
+    pub fn next_unsigned_raw(&mut self) -> Option<(u64, u128, u32)> {
+        self.next_unsigned_with_prefix()
+            .map(|(value, lz)| (value, value as u128 + 1, 2 * lz + 1))
+    }
+
+    /// Read the next Exp-Golomb value as an unsigned integer, returning a [`DecodeError`] that
+    /// says why and where decoding failed instead of `None`. See
+    /// [`ExpGolombDecoder::next_unsigned`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::{DecodeErrorKind, ExpGolombDecoder};
+    /// // 010 - 1, 00110 - 5, 0000 - incomplete
+    /// let data = [0b01000110, 0b00000000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.try_next_unsigned(), Ok(1));
+    /// assert_eq!(reader.try_next_unsigned(), Ok(5));
+    ///
+    /// let err = reader.try_next_unsigned().unwrap_err();
+    /// assert_eq!(err.kind, DecodeErrorKind::EndOfStream);
+    /// assert_eq!(err.at_bit, 8);
+    /// assert_eq!(err.to_string(), "bitstream ended in the middle of a codeword at bit 8");
+    /// ```
+    #[inline]
+    pub fn try_next_unsigned(&mut self) -> Result<u64, DecodeError> {
+        let at_bit = self.iter.bit_offset();
+        self.try_next_unsigned_with_prefix()
+            .map(|(value, _)| value)
+            .map_err(|kind| DecodeError { kind, at_bit })
+    }
+
+    /// Returns an iterator over the remaining unsigned values that ends cleanly at the end of the
+    /// bitstream, including in the middle of a codeword, e.g. at the RBSP trailing bits. A codeword
+    /// that is too long to be decoded instead yields an error of kind
+    /// [`DecodeErrorKind::Overflow`](crate::DecodeErrorKind::Overflow), after which the iterator only
+    /// returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::{DecodeError, DecodeErrorKind, ExpGolombDecoder};
+    /// // 010 - 1, 00110 - 5, 0000 - incomplete
+    /// let data = [0b01000110, 0b00000000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// let values: Vec<_> = reader.try_unsigned_iter().collect();
+    /// assert_eq!(values, [Ok(1), Ok(5)]);
+    ///
+    /// // 010 - 1, 66 leading zeros
+    /// let data = [0b01000000, 0, 0, 0, 0, 0, 0, 0, 0, 0b10000000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// let values: Vec<_> = reader.try_unsigned_iter().collect();
+    /// let overflow = DecodeError {
+    ///     kind: DecodeErrorKind::Overflow,
+    ///     at_bit: 3,
+    /// };
+    /// assert_eq!(values, [Ok(1), Err(overflow)]);
+    /// ```
+    #[inline]
+    pub fn try_unsigned_iter(
+        &mut self,
+    ) -> impl Iterator<Item = Result<u64, DecodeError>> + use<'_, 'a> {
+        let mut done = false;
+        core::iter::from_fn(move || {
+            if done {
+                return None;
+            }
+            match self.try_next_unsigned() {
+                Ok(value) => Some(Ok(value)),
+                Err(err) => {
+                    done = true;
+                    match err.kind {
+                        DecodeErrorKind::EndOfStream => None,
+                        DecodeErrorKind::Overflow => Some(Err(err)),
+                    }
+                }
+            }
+        })
+    }
+
+    /// Returns an iterator over the remaining unsigned values. It stops at the first codeword that
+    /// cannot be decoded, i.e. when [`ExpGolombDecoder::next_unsigned`] returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// let data = [0b01000110, 0b00000000, 0b11111111, 0b10010101];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// let values: Vec<u64> = reader.unsigned_iter().collect();
+    /// assert_eq!(values, [1, 5, 510, 4]);
+    /// ```
+    #[inline]
+    pub fn unsigned_iter(&mut self) -> UnsignedIter<'_, 'a> {
+        UnsignedIter { decoder: self }
+    }
+
+    /// Returns an iterator over the remaining unsigned values paired with a flag that is `true`
+    /// only for the last value, i.e. the one after which [`ExpGolombDecoder::next_unsigned`] would
+    /// return `None`.
+    ///
+    /// The iterator reads one value ahead to know whether the current one is the last, so the
+    /// decoder is one value further along than the values taken from the iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 010 - 1, 00110 - 5, 0000 - incomplete
+    /// let data = [0b01000110, 0b00000000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// let values: Vec<_> = reader.unsigned_iter_flagged().collect();
+    /// assert_eq!(values, [(1, false), (5, true)]);
+    /// ```
+    #[inline]
+    pub fn unsigned_iter_flagged(&mut self) -> impl Iterator<Item = (u64, bool)> + use<'_, 'a> {
+        let mut peeked = None;
+        core::iter::from_fn(move || {
+            let value = match peeked.take() {
+                Some(value) => value,
+                None => self.next_unsigned(),
+            }?;
+            let next = self.next_unsigned();
+            peeked = Some(next);
+            Some((value, next.is_none()))
+        })
+    }
+
+    /// Read the next Exp-Golomb value as a `u32`. Returns `None` if the end of the bitstream is
+    /// reached before parsing is completed or if the coded value exceeds the limits of a `u32`.
+    ///
+    /// This is a specialization of [`ExpGolombDecoder::next_unsigned`] for fields that are known to
+    /// be small. The decoded bits are accumulated in 32-bit integers, which can produce better code
+    /// on 32-bit targets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 00110 - 5, 32 leading zeros followed by `1` and 32 zeros - u32::MAX
+    /// let data = [
+    ///     0b00110000, 0b00000000, 0b00000000, 0b00000000, 0b00000100, 0b00000000, 0b00000000,
+    ///     0b00000000, 0b00000000,
+    /// ];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.next_unsigned_small(), Some(5));
+    /// assert_eq!(reader.next_unsigned_small(), Some(u32::MAX));
+    ///
+    /// // u32::MAX + 1 does not fit
+    /// let data = [0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b10000000, 0, 0, 0, 0b10000000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.next_unsigned_small(), None);
+    /// ```
+    #[inline]
+    #[must_use = "use `ExpGolombReader::skip_next` if the value is not needed"]
+    pub fn next_unsigned_small(&mut self) -> Option<u32> {
+        if let Some((value, _)) = self.next_unsigned_within_byte() {
+            return Some(value as u32);
+        }
+        let result = read_unsigned_small(&mut self.iter, self.max_leading_zeros);
+        if result.is_none() {
+            self.iter.move_to_end();
+        }
+        result
+    }
+
+    /// Read the next Exp-Golomb value, interpreting it as a signed integer. Returns `None` if the
+    /// end of the bitstream is reached before parsing is completed or if the coded value is
+    /// exceeds the limits of a `i64`.
+    ///
+    /// Code numbers are mapped positive first as in the `se(v)` of H.264, i.e. 1 is read as 1, 2
+    /// as -1, 3 as 2 and so on. See [`ExpGolombDecoder::next_signed_neg_first`] for the opposite
+    /// convention.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // Concatenated Wikipedia example:
+    /// // https://en.wikipedia.org/wiki/Exponential-Golomb_coding#Extension_to_negative_numbers
+    /// let data = [0b10100110, 0b01000010, 0b10011000, 0b11100010, 0b00000100, 0b10000000];
+    ///
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.next_signed(), Some(0));
+    /// assert_eq!(reader.next_signed(), Some(1));
+    /// assert_eq!(reader.next_signed(), Some(-1));
+    /// assert_eq!(reader.next_signed(), Some(2));
+    /// assert_eq!(reader.next_signed(), Some(-2));
+    /// assert_eq!(reader.next_signed(), Some(3));
+    /// assert_eq!(reader.next_signed(), Some(-3));
+    /// assert_eq!(reader.next_signed(), Some(4));
+    /// assert_eq!(reader.next_signed(), Some(-4));
+    /// assert_eq!(reader.next_signed(), None);
+    /// assert_eq!(reader.next_signed(), None);
+    /// ```
+    ///
+    /// The coded value is limited to 64 bits. Trying to parse larger values would return
+    /// `None`.
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 64 leading zeros followed by `1` and 64 zeros
+    /// let data = [
+    ///     0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000,
+    ///     0b00000000, 0b00000001, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000,
+    ///     0b00000000, 0b00000000, 0b00000000,
+    /// ];
+    /// let mut reader = ExpGolombDecoder::new(&data, 7).unwrap();
+    /// assert_eq!(reader.next_signed(), Some(i64::MIN));
+    ///
+    /// // Attempt to parse a 65-bit number
+    /// let mut reader = ExpGolombDecoder::new(&data, 6).unwrap();
+    /// assert_eq!(reader.next_signed(), None);
+    ///
+    /// // Any other suffix after 64 leading zeros is also too large
+    /// let mut data = data;
+    /// data[16] = 0b00000001;
+    /// let mut reader = ExpGolombDecoder::new(&data, 7).unwrap();
+    /// assert_eq!(reader.next_signed(), None);
+    /// ```
+    #[inline]
+    #[must_use = "use `ExpGolombReader::skip_next` if the value is not needed"]
+    pub fn next_signed(&mut self) -> Option<i64> {
+        self.next_unsigned().map(unsigned_to_signed)
+    }
+
+    /// Read the next two Exp-Golomb values as signed integers, e.g. the components of a motion
+    /// vector difference. Returns `None` if either of them cannot be read, in which case the
+    /// decoder is left at the end of the bitstream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 010 - 1, 00101 - -2, 011 - -1
+    /// let data = [0b01000101, 0b01100000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.next_signed_pair(), Some((1, -2)));
+    /// assert_eq!(reader.next_signed_pair(), None);
+    /// assert_eq!(reader.remaining_bits(), 0);
+    /// ```
+    #[inline]
+    #[must_use = "use `ExpGolombReader::skip_next` if the value is not needed"]
+    pub fn next_signed_pair(&mut self) -> Option<(i64, i64)> {
+        Some((self.next_signed()?, self.next_signed()?))
+    }
+
+    /// Read the next two Exp-Golomb values as unsigned integers, e.g. a pair of coordinates.
+    /// Returns `None` under the same conditions as [`ExpGolombDecoder::next_signed_pair`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 010 - 1, 00110 - 5
+    /// let data = [0b01000110];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.next_unsigned_pair(), Some((1, 5)));
+    /// ```
+    #[inline]
+    #[must_use = "use `ExpGolombReader::skip_next` if the value is not needed"]
+    pub fn next_unsigned_pair(&mut self) -> Option<(u64, u64)> {
+        Some((self.next_unsigned()?, self.next_unsigned()?))
+    }
+
+    /// Read the next Exp-Golomb value, interpreting it as a signed integer with negative values
+    /// first, i.e. 1 is read as -1, 2 as 1, 3 as -2 and so on. Returns `None` under the same
+    /// conditions as [`ExpGolombDecoder::next_signed`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 1 - 0, 010 - 1, 011 - 2, 00100 - 3
+    /// let data = [0b10100110, 0b01000000];
+    ///
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.next_signed_neg_first(), Some(0));
+    /// assert_eq!(reader.next_signed_neg_first(), Some(-1));
+    /// assert_eq!(reader.next_signed_neg_first(), Some(1));
+    /// assert_eq!(reader.next_signed_neg_first(), Some(-2));
+    /// ```
+    #[inline]
+    #[must_use = "use `ExpGolombReader::skip_next` if the value is not needed"]
+    pub fn next_signed_neg_first(&mut self) -> Option<i64> {
+        self.next_unsigned().map(unsigned_to_signed_neg_first)
+    }
+
+    /// Read the next Exp-Golomb value as a signed integer and convert it to `T`. Returns `None`
+    /// under the same conditions as [`ExpGolombDecoder::next_signed`] or if the value does not fit
+    /// in `T`, in which case the codeword is still consumed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 00101 - -2, 00000000111111111 - -255
+    /// let data = [0b00101000, 0b00000111, 0b11111100];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// let delta: i8 = reader.next_signed_as().unwrap();
+    /// assert_eq!(delta, -2);
+    /// assert_eq!(reader.next_signed_as::<i8>(), None);
+    /// ```
+    #[inline]
+    #[must_use = "use `ExpGolombReader::skip_next` if the value is not needed"]
+    pub fn next_signed_as<T: TryFrom<i64>>(&mut self) -> Option<T> {
+        self.next_signed().and_then(|value| T::try_from(value).ok())
+    }
+
+    /// Read the next Exp-Golomb value as an unsigned fixed-point number with `frac_bits`
+    /// fractional bits, i.e. the decoded integer divided by `2^frac_bits`. Returns `None` under the
+    /// same conditions as [`ExpGolombDecoder::next_unsigned`] or if `frac_bits` is more than 64, in
+    /// which case nothing is read.
+    ///
+    /// Scaling by a power of two is exact, so the only rounding is in converting the integer to an
+    /// `f64`. Integers above 2^53 are rounded to the nearest representable `f64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 00110 - 5, 00110 - 5
+    /// let data = [0b00110001, 0b10000000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.next_unsigned_fixed_point(2), Some(1.25));
+    /// assert_eq!(reader.next_unsigned_fixed_point(0), Some(5.0));
+    /// ```
+    #[inline]
+    #[must_use = "use `ExpGolombReader::skip_next` if the value is not needed"]
+    pub fn next_unsigned_fixed_point(&mut self, frac_bits: u32) -> Option<f64> {
+        let scale = fixed_point_scale(frac_bits)?;
+        let value = self.next_unsigned()?;
+        Some(value as f64 / scale)
+    }
+
+    /// Read the next Exp-Golomb value as a signed fixed-point number with `frac_bits` fractional
+    /// bits, using the same mapping as [`ExpGolombDecoder::next_signed`]. Returns `None` under the
+    /// same conditions as [`ExpGolombDecoder::next_unsigned_fixed_point`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 00111 - -3
+    /// let data = [0b00111000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.next_signed_fixed_point(3), Some(-0.375));
+    /// ```
+    #[inline]
+    #[must_use = "use `ExpGolombReader::skip_next` if the value is not needed"]
+    pub fn next_signed_fixed_point(&mut self, frac_bits: u32) -> Option<f64> {
+        let scale = fixed_point_scale(frac_bits)?;
+        let value = self.next_signed()?;
+        Some(value as f64 / scale)
+    }
+
+    /// Fill `out` with consecutive unsigned values, returning how many were written. The count is
+    /// less than `out.len()` only if the end of the bitstream is reached or a coded value exceeds
+    /// the limits of a `u64`, in which case the rest of `out` is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// let data = [0b01000110, 0b00000000, 0b11111111, 0b10010101];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    ///
+    /// let mut out = [0; 8];
+    /// assert_eq!(reader.next_unsigned_into(&mut out), 4);
+    /// assert_eq!(out, [1, 5, 510, 4, 0, 0, 0, 0]);
+    /// ```
+    #[inline]
+    pub fn next_unsigned_into(&mut self, out: &mut [u64]) -> usize {
+        for (i, value) in out.iter_mut().enumerate() {
+            match self.next_unsigned() {
+                Some(v) => *value = v,
+                None => return i,
+            }
+        }
+        out.len()
+    }
+
+    /// Fill `out` with consecutive signed values, returning how many were written. The count is
+    /// less than `out.len()` only if the end of the bitstream is reached or a coded value exceeds
+    /// the limits of a `i64`, in which case the rest of `out` is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// let data = [0b10100110, 0b01000010, 0b10011000, 0b11100010, 0b00000100, 0b10000000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    ///
+    /// let mut out = [0; 4];
+    /// assert_eq!(reader.next_signed_into(&mut out), 4);
+    /// assert_eq!(out, [0, 1, -1, 2]);
+    ///
+    /// let mut out = [0; 8];
+    /// assert_eq!(reader.next_signed_into(&mut out), 5);
+    /// assert_eq!(out, [-2, 3, -3, 4, -4, 0, 0, 0]);
+    /// ```
+    #[inline]
+    pub fn next_signed_into(&mut self, out: &mut [i64]) -> usize {
+        for (i, value) in out.iter_mut().enumerate() {
+            match self.next_signed() {
+                Some(v) => *value = v,
+                None => return i,
+            }
+        }
+        out.len()
+    }
+
+    /// Read an unsigned value giving the length in bits of the structure that follows, parse the
+    /// structure with `f` and check that it consumed exactly that many bits. Returns `None` if the
+    /// length cannot be read, if `f` returns `None` or if the number of bits consumed by `f` does
+    /// not match the length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 0001001 - 8, 010 - 1, 00110 - 5
+    /// let data = [0b00010010, 0b10001100];
+    ///
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// let pair = reader.with_length_prefix(|r| Some((r.next_unsigned()?, r.next_unsigned()?)));
+    /// assert_eq!(pair, Some((1, 5)));
+    ///
+    /// // Reading only the first value is a misparse
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.with_length_prefix(|r| r.next_unsigned()), None);
+    /// ```
+    #[inline]
+    pub fn with_length_prefix<T>(&mut self, f: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+        let len = self.next_unsigned()?;
+        let start = self.iter.bit_offset();
+        let value = f(self)?;
+        // `f` may have seeked back past the start
+        let consumed = self.iter.bit_offset().checked_sub(start);
+        if consumed.map(|n| n as u64) == Some(len) {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Read a list that is prefixed by its length, i.e. an unsigned count followed by that many
+    /// unsigned values. Returns `None` if any of the reads fails, which includes a count that is
+    /// larger than the rest of the bitstream could hold.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 011 - 2, 010 - 1, 00110 - 5, 1 - 0, 011 - 2
+    /// let data = [0b01101000, 0b11010110];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.next_unsigned_vec(), Some(vec![1, 5]));
+    /// assert_eq!(reader.next_unsigned_vec(), Some(vec![]));
+    /// assert_eq!(reader.next_unsigned_vec(), None);
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    #[must_use = "use `ExpGolombReader::skip_next` if the value is not needed"]
+    pub fn next_unsigned_vec(&mut self) -> Option<Vec<u64>> {
+        let count = self.next_list_len()?;
+        (0..count).map(|_| self.next_unsigned()).collect()
+    }
+
+    /// Read a list of signed values that is prefixed by its length as an unsigned count. Returns
+    /// `None` under the same conditions as [`ExpGolombDecoder::next_unsigned_vec`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 011 - 2, 011 - -1, 00111 - -3
+    /// let data = [0b01101100, 0b11100000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.next_signed_vec(), Some(vec![-1, -3]));
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    #[must_use = "use `ExpGolombReader::skip_next` if the value is not needed"]
+    pub fn next_signed_vec(&mut self) -> Option<Vec<i64>> {
+        let count = self.next_list_len()?;
+        (0..count).map(|_| self.next_signed()).collect()
+    }
+
+    /// Reads the count of a length-prefixed list, rejecting counts that cannot fit in the rest of
+    /// the bitstream since every codeword takes at least one bit.
+    #[cfg(feature = "std")]
+    #[inline]
+    fn next_list_len(&mut self) -> Option<usize> {
+        let count = self.next_unsigned()?;
+        let end = (self.iter.end_index as u64 * 8) + self.iter.end_bit_pos as u64;
+        let remaining_bits = end - self.iter.bit_offset() as u64;
+        if count > remaining_bits {
+            self.iter.move_to_end();
+            return None;
+        }
+        Some(count as usize)
+    }
+
+    /// Decode `count` signed values as deltas and push their running sum to `out`, e.g. to
+    /// reconstruct delta-coded scaling lists. The first value is a delta from 0. Returns how many
+    /// values were pushed, which is less than `count` under the same conditions as
+    /// [`ExpGolombDecoder::next_signed_into`]. The running sum wraps around on overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // Deltas of 0, 1, -1, 2, -2, 3, -3, 4, -4
+    /// let data = [0b10100110, 0b01000010, 0b10011000, 0b11100010, 0b00000100, 0b10000000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    ///
+    /// let mut out = Vec::new();
+    /// assert_eq!(reader.decode_deltas_signed(5, &mut out), 5);
+    /// assert_eq!(out, [0, 1, 0, 2, 0]);
+    ///
+    /// // Each call starts again from 0
+    /// assert_eq!(reader.decode_deltas_signed(8, &mut out), 4);
+    /// assert_eq!(out, [0, 1, 0, 2, 0, 3, 0, 4, 0]);
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn decode_deltas_signed(&mut self, count: usize, out: &mut Vec<i64>) -> usize {
+        let mut sum = 0i64;
+        for i in 0..count {
+            match self.next_signed() {
+                Some(delta) => {
+                    sum = sum.wrapping_add(delta);
+                    out.push(sum);
+                }
+                None => return i,
+            }
+        }
+        count
+    }
+
+    /// Decode unsigned values and push them to `out` for as long as `pred` returns `true` for them,
+    /// e.g. for a list that ends with the first value outside of a range. The value for which
+    /// `pred` returns `false` is not consumed and can be read next. Decoding also stops without
+    /// consuming anything at a codeword that cannot be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 010 - 1, 00110 - 5, 00000000111111111 - 510, 00101 - 4
+    /// let data = [0b01000110, 0b00000000, 0b11111111, 0b10010100];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    ///
+    /// let mut out = Vec::new();
+    /// reader.unsigned_while(|value| value < 100, &mut out);
+    /// assert_eq!(out, [1, 5]);
+    /// assert_eq!(reader.next_unsigned(), Some(510));
+    ///
+    /// reader.unsigned_while(|_| true, &mut out);
+    /// assert_eq!(out, [1, 5, 4]);
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn unsigned_while(&mut self, pred: impl Fn(u64) -> bool, out: &mut Vec<u64>) {
+        loop {
+            let mut peek = self.iter.detached();
+            match try_read_unsigned_with_prefix(&mut peek, self.max_leading_zeros) {
+                Ok((value, _)) if pred(value) => {
+                    self.iter.advance_to((peek.index, peek.bit_pos));
+                    out.push(value);
+                }
+                _ => return,
+            }
+        }
+    }
+
+    /// Read a field for each of `specs` in order and push their values to `out`, e.g. to parse a
+    /// syntax structure described by a table. Unsigned values are converted to `i64`.
+    ///
+    /// Returns `None` if a field cannot be read under the same conditions as the corresponding
+    /// method of the decoder or if an unsigned value does not fit in an `i64`. The fields before
+    /// it have been pushed to `out` by then, so its length tells how many were read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::{ExpGolombDecoder, FieldSpec};
+    /// // 010 - 1, 011 - -1, 1010 - 10, 1 - 0
+    /// let data = [0b01001110, 0b10100000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    ///
+    /// use FieldSpec::{Fixed, Signed, Unsigned};
+    ///
+    /// let mut out = Vec::new();
+    /// reader.decode_fields(&[Unsigned, Signed, Fixed(4), Unsigned], &mut out).unwrap();
+    /// assert_eq!(out, [1, -1, 10, 0]);
+    ///
+    /// // Only the first field fits in the remaining bits
+    /// assert!(reader.decode_fields(&[Fixed(2), Unsigned], &mut out).is_none());
+    /// assert_eq!(out, [1, -1, 10, 0, 0]);
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn decode_fields(&mut self, specs: &[FieldSpec], out: &mut Vec<i64>) -> Option<()> {
+        for spec in specs {
+            let value = match *spec {
+                FieldSpec::Unsigned => i64::try_from(self.next_unsigned()?).ok()?,
+                FieldSpec::Signed => self.next_signed()?,
+                FieldSpec::Fixed(num_bits) => i64::try_from(self.next_n_bits(num_bits)?).ok()?,
+            };
+            out.push(value);
+        }
+        Some(())
+    }
+
+    /// Split the remaining bitstream in two at `num_bits` after the current position. The first
+    /// decoder reads only the next `num_bits` bits while the second one starts right after them,
+    /// which need not be at a byte boundary. Neither decoder can seek into the bits of the other
+    /// one. Returns `None` if fewer than `num_bits` bits remain. Neither decoder keeps the observer
+    /// set with `set_bit_observer`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 010 00110 | 00101 011
+    /// let data = [0b01000110, 0b00101011];
+    /// let reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// let (mut first, mut second) = reader.split_at_bit(8).unwrap();
+    ///
+    /// assert_eq!(first.next_unsigned(), Some(1));
+    /// assert_eq!(first.next_unsigned(), Some(5));
+    /// assert_eq!(first.next_unsigned(), None);
+    ///
+    /// assert_eq!(second.next_unsigned(), Some(4));
+    /// assert_eq!(second.next_unsigned(), Some(2));
+    /// assert_eq!(second.next_unsigned(), None);
+    /// ```
+    ///
+    /// The split point does not have to be byte-aligned:
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 010 | 00110 00101 011
+    /// let data = [0b01000110, 0b00101011];
+    /// let reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// let (mut first, mut second) = reader.split_at_bit(3).unwrap();
+    /// assert_eq!(first.next_unsigned(), Some(1));
+    /// assert_eq!(second.next_unsigned(), Some(5));
+    ///
+    /// let reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert!(reader.split_at_bit(17).is_none());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn split_at_bit(
+        self,
+        num_bits: usize,
+    ) -> Option<(ExpGolombDecoder<'a>, ExpGolombDecoder<'a>)> {
+        let (index, bit_pos) = self.iter.offset_position(num_bits)?;
+        let first = BitIterator {
+            end_index: index,
+            end_bit_pos: bit_pos,
+            ..self.iter.detached()
+        };
+        let second = BitIterator {
+            index,
+            bit_pos,
+            start_index: index,
+            start_bit_pos: bit_pos,
+            ..self.iter.detached()
+        };
+        Some((
+            ExpGolombDecoder {
+                iter: first,
+                max_leading_zeros: self.max_leading_zeros,
+            },
+            ExpGolombDecoder {
+                iter: second,
+                max_leading_zeros: self.max_leading_zeros,
+            },
+        ))
+    }
+
+    /// Skip the next Exp-Golomb encoded value. Any parsing error at the end of the bitstream is
+    /// ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// let data = [0b01001001, 0b00110000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// reader.skip_next();
+    /// reader.skip_next();
+    /// reader.skip_next();
+    /// assert_eq!(reader.next_unsigned(), Some(2));
+    /// reader.skip_next();
+    /// assert_eq!(reader.next_unsigned(), None);
+    /// reader.skip_next();
+    /// assert_eq!(reader.next_unsigned(), None);
+    /// ```
+    #[inline]
+    pub fn skip_next(&mut self) {
+        let _ = self.try_skip_next();
+    }
+
+    /// Skip up to `n` Exp-Golomb encoded values, returning how many were skipped. The count is
+    /// less than `n` only if the bitstream ended before all the values were skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// let data = [0b01001001, 0b00110000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.skip_n_values(3), 3);
+    /// assert_eq!(reader.next_unsigned(), Some(2));
+    ///
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.skip_n_values(10), 4);
+    /// ```
+    #[inline]
+    pub fn skip_n_values(&mut self, n: usize) -> usize {
+        for i in 0..n {
+            if self.try_skip_next().is_err() {
+                return i;
+            }
+        }
+        n
+    }
+
+    /// Skip the next Exp-Golomb encoded value, returning the length of its codeword in bits.
+    /// Returns `None` if the end of the bitstream is reached before the whole codeword is skipped
+    /// or if the codeword is too long to be decoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 1 - 0, 00110 - 5, 01 - incomplete
+    /// let data = [0b10011001];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.skip_next_len(), Some(1));
+    /// assert_eq!(reader.skip_next_len(), Some(5));
+    /// assert_eq!(reader.skip_next_len(), None);
+    /// ```
+    #[inline]
+    pub fn skip_next_len(&mut self) -> Option<u32> {
+        self.try_skip_next().ok()
+    }
+
+    #[inline]
+    fn try_skip_next(&mut self) -> Result<u32, DecodeErrorKind> {
+        let lz = match try_count_leading_zeroes(&mut self.iter, self.max_leading_zeros) {
+            Ok(lz) => lz,
+            Err(err) => {
+                self.iter.move_to_end();
+                return Err(err);
+            }
+        };
+        self.iter
+            .skip_bits(lz)
+            .ok_or(DecodeErrorKind::EndOfStream)?;
+        Ok(2 * lz + 1)
+    }
+
+    /// Returns the position of the next bit to be read.
+    ///
+    /// A read that fails to decode, i.e. that reaches the end of the bitstream or a codeword that
+    /// is too long, leaves the decoder at the end of the bitstream, so the position is then
+    /// `(buf.len(), 0)` or the end bit of a bounded decoder. Reads that return `None` because a
+    /// decoded value or an argument is rejected document where they leave the decoder instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::{BitPosition, ExpGolombDecoder};
+    /// let data = [0b01000000, 0b00000000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 1).unwrap();
+    /// assert_eq!(reader.position(), BitPosition::new(0, 1));
+    /// assert_eq!(reader.next_unsigned(), Some(0));
+    /// assert_eq!(reader.position(), BitPosition::new(0, 2));
+    ///
+    /// // Runs out of bits in the middle of the codeword
+    /// assert_eq!(reader.next_unsigned(), None);
+    /// assert_eq!(reader.position(), BitPosition::new(2, 0));
+    /// ```
+    #[inline]
+    pub fn position(&self) -> BitPosition {
+        BitPosition {
+            byte_index: self.iter.index,
+            bit_offset: self.iter.bit_pos as u8,
+        }
+    }
+
+    /// Returns the position of the next bit to be read as a single number of bits from the most
+    /// significant bit of `buf[0]`, e.g. to store it or to compute the distance between two
+    /// positions. It can be passed to [`ExpGolombDecoder::new_at`] to resume decoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 010 - 1, 00110 - 5, 011 - 2
+    /// let data = [0b01000110, 0b01100000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.next_unsigned(), Some(1));
+    /// let saved = reader.position_bits();
+    /// assert_eq!(saved, 3);
+    /// assert_eq!(reader.next_unsigned(), Some(5));
+    /// assert_eq!(reader.position_bits() - saved, 5);
+    ///
+    /// let mut resumed = ExpGolombDecoder::new_at(&data, saved).unwrap();
+    /// assert_eq!(resumed.next_unsigned(), Some(5));
+    /// ```
+    #[inline]
+    pub fn position_bits(&self) -> u64 {
+        self.iter.index as u64 * 8 + self.iter.bit_pos as u64
+    }
+
+    /// Returns the byte containing the next bit to be read, e.g. to display it alongside the bit
+    /// offset from [`ExpGolombDecoder::position`]. Returns `None` at the end of the bitstream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// let data = [0b01000000, 0b10000000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.current_byte(), Some(0b01000000));
+    /// assert_eq!(reader.next_unsigned(), Some(1));
+    /// assert_eq!(reader.current_byte(), Some(0b01000000));
+    /// assert_eq!(reader.next_unsigned(), Some(31));
+    /// assert_eq!(reader.current_byte(), Some(0b10000000));
+    /// assert_eq!(reader.next_unsigned(), None);
+    /// assert_eq!(reader.current_byte(), None);
+    /// ```
+    #[inline]
+    pub fn current_byte(&self) -> Option<u8> {
+        if self.iter.is_at_end() {
+            None
+        } else {
+            Some(self.iter.buf[self.iter.index])
+        }
+    }
+
+    /// Returns the length of the bitstream in bits, counted from the most significant bit of
+    /// `buf[0]`. This is `buf.len() * 8` unless the end was moved with
+    /// [`ExpGolombDecoder::new_bounded`] or [`ExpGolombDecoder::split_at_bit`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// let data = [0b01000110, 0b10000000];
+    /// let reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.len_bits(), 16);
+    ///
+    /// let reader = ExpGolombDecoder::new_bounded(&data, 0, 9).unwrap();
+    /// assert_eq!(reader.len_bits(), 9);
+    /// ```
+    #[inline]
+    pub fn len_bits(&self) -> usize {
+        self.iter.end_index * 8 + self.iter.end_bit_pos as usize
+    }
+
+    /// Returns the number of bits left before the end of the bitstream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// let data = [0b01000110, 0b10000000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 1).unwrap();
+    /// assert_eq!(reader.remaining_bits(), 15);
+    /// assert_eq!(reader.next_unsigned(), Some(0));
+    /// assert_eq!(reader.remaining_bits(), 14);
+    /// ```
+    #[inline]
+    pub fn remaining_bits(&self) -> usize {
+        self.iter.remaining_bits()
+    }
+
+    /// Move the decoder to `pos`, which may be before or after the current position. Returns
+    /// `None` if `pos.bit_offset` is not within \[0, 7\] or if `pos` is before the position the
+    /// decoder was created at or past the end of the bitstream, in which case the decoder is not
+    /// moved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::{BitPosition, ExpGolombDecoder};
+    /// // 010 - 1, 00110 - 5
+    /// let data = [0b01000110];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// let start = reader.position();
+    /// assert_eq!(reader.next_unsigned(), Some(1));
+    /// assert_eq!(reader.next_unsigned(), Some(5));
+    ///
+    /// reader.seek(start).unwrap();
+    /// assert_eq!(reader.next_unsigned(), Some(1));
+    /// assert!(reader.seek(BitPosition::new(1, 1)).is_none());
+    /// assert_eq!(reader.next_unsigned(), Some(5));
+    ///
+    /// let mut reader = ExpGolombDecoder::new(&data, 3).unwrap();
+    /// assert!(reader.seek(BitPosition::new(0, 0)).is_none());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn seek(&mut self, pos: BitPosition) -> Option<()> {
+        let target = (pos.byte_index, pos.bit_offset as u32);
+        if target.1 > 7
+            || target < (self.iter.start_index, self.iter.start_bit_pos)
+            || target > (self.iter.end_index, self.iter.end_bit_pos)
+        {
+            return None;
+        }
+        (self.iter.index, self.iter.bit_pos) = target;
+        Some(())
+    }
+
+    /// Move the decoder to the byte after the next `0x000001` start code of the H.264/H.265 Annex B
+    /// byte stream format, which also covers the four-byte `0x00000001` form. The search starts at
+    /// the current byte if the decoder is at a byte boundary. If it is in the middle of a byte, the
+    /// rest of that byte is skipped and the search starts at the next one. Returns `None` if there
+    /// is no start code before the end, in which case the decoder is not moved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// let data = [0xFF, 0x00, 0x00, 0x00, 0x01, 0b01000000, 0x00, 0x00, 0x01];
+    /// let mut reader = ExpGolombDecoder::new(&data, 3).unwrap();
+    /// reader.seek_next_start_code().unwrap();
+    /// assert_eq!(reader.next_unsigned(), Some(1));
+    ///
+    /// reader.seek_next_start_code().unwrap();
+    /// assert_eq!(reader.remaining_bits(), 0);
+    /// assert!(reader.seek_next_start_code().is_none());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn seek_next_start_code(&mut self) -> Option<()> {
+        let from = self.iter.index + (self.iter.bit_pos != 0) as usize;
+        let bytes = self.iter.buf.get(from..self.iter.end_index)?;
+        let offset = bytes
+            .windows(3)
+            .position(|window| window == [0x00, 0x00, 0x01])?;
+        self.iter.advance_to((from + offset + 3, 0));
+        Some(())
+    }
+
+    /// Returns an independent decoder over the same bitstream that starts at bit `bit_pos` of
+    /// `buf[index]`, e.g. to follow a back-reference without losing the current position. The new
+    /// decoder keeps the end of the bitstream and the codeword limit but not the observer. Returns
+    /// `None` under the same conditions as [`ExpGolombDecoder::seek`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 010 - 1, 00110 - 5
+    /// let data = [0b01000110];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.next_unsigned(), Some(1));
+    ///
+    /// let mut back = reader.clone_at(0, 0).unwrap();
+    /// assert_eq!(back.next_unsigned(), Some(1));
+    /// assert_eq!(reader.next_unsigned(), Some(5));
+    /// assert!(reader.clone_at(0, 8).is_none());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn clone_at(&self, index: usize, bit_pos: u32) -> Option<ExpGolombDecoder<'a>> {
+        let mut decoder = ExpGolombDecoder {
+            iter: self.iter.detached(),
+            max_leading_zeros: self.max_leading_zeros,
+        };
+        decoder.seek(BitPosition {
+            byte_index: index,
+            bit_offset: bit_pos.try_into().ok()?,
+        })?;
+        Some(decoder)
+    }
+
+    /// Borrow the decoder as an iterator over its bits. Bits taken through the iterator are
+    /// consumed from the decoder, so Exp-Golomb decoding resumes right after them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 101 - flags, 00110 - 5
+    /// let data = [0b10100110];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    ///
+    /// let flags: Vec<u8> = reader.as_bit_reader().take(3).collect();
+    /// assert_eq!(flags, [1, 0, 1]);
+    /// assert_eq!(reader.next_unsigned(), Some(5));
+    /// ```
+    #[inline]
+    pub fn as_bit_reader(&mut self) -> BitReaderRef<'_, 'a> {
+        BitReaderRef {
+            iter: &mut self.iter,
+        }
+    }
+
+    /// Returns the rest of the bitstream as bytes so that it can be handed off to another parser.
+    /// Returns `None` if the current position or the end of the bitstream is not at a byte
+    /// boundary since the partially read byte cannot be represented as a slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// let data = [0b00111000, 0xAB, 0xCD];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert_eq!(reader.next_unsigned(), Some(6));
+    /// assert_eq!(reader.remaining_bytes(), None);
+    /// assert_eq!(reader.next_bit(), Some(0));
+    /// assert_eq!(reader.next_bit(), Some(0));
+    /// assert_eq!(reader.next_bit(), Some(0));
+    /// assert_eq!(reader.remaining_bytes(), Some(&data[1..]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn remaining_bytes(&self) -> Option<&'a [u8]> {
+        if self.iter.bit_pos == 0 && self.iter.end_bit_pos == 0 {
+            Some(&self.iter.buf[self.iter.index..self.iter.end_index])
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether there is more data before the RBSP trailing bits, i.e. the `more_rbsp_data()`
+    /// function of H.264 and H.265. This is `true` if the last `1` bit in the rest of the
+    /// bitstream, which is taken to be the RBSP stop bit, is not the next bit to be read. Nothing
+    /// is consumed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombDecoder;
+    /// // 010 - 1, followed by the stop bit and zeros
+    /// let data = [0b01010000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    /// assert!(reader.more_rbsp_data());
+    /// assert_eq!(reader.next_unsigned(), Some(1));
+    /// assert!(!reader.more_rbsp_data());
+    /// ```
+    #[inline]
+    pub fn more_rbsp_data(&self) -> bool {
+        matches!(self.iter.last_set_bit(), Some(pos) if pos != (self.iter.index, self.iter.bit_pos))
+    }
+}
+
+/// Decode every unsigned value in `buf`, returning `(start_bit, end_bit, value)` for each codeword
+/// where `start_bit` is the offset of its first bit and `end_bit` is one past its last bit. Offsets
+/// are counted from the most significant bit of `buf[0]`. Decoding stops at the first codeword
+/// that cannot be parsed, and nothing is returned if `buf` is empty or `start` is not within
+/// \[0, 7\].
+///
+/// Offsets are in bits, so on 32-bit targets `buf` must be shorter than 512 MiB for them to fit in
+/// a `usize`.
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::decode_trace_unsigned;
+/// // 010 00110 1 0000000 - 1, 5, 0 and trailing bits
+/// let data = [0b01000110, 0b10000000];
+/// assert_eq!(
+///     decode_trace_unsigned(&data, 0),
+///     vec![(0, 3, 1), (3, 8, 5), (8, 9, 0)]
+/// );
+/// ```
+#[cfg(feature = "std")]
+pub fn decode_trace_unsigned(buf: &[u8], start: u32) -> Vec<(usize, usize, u64)> {
+    let mut trace = Vec::new();
+    if let Some(mut reader) = ExpGolombDecoder::new(buf, start) {
+        loop {
+            let start_bit = reader.iter.bit_offset();
+            match reader.next_unsigned() {
+                Some(value) => trace.push((start_bit, reader.iter.bit_offset(), value)),
+                None => break,
+            }
+        }
+    }
+    trace
+}
+
+/// Decode every unsigned value in `buf`, stopping at the first codeword that cannot be parsed.
+/// Nothing is returned if `buf` is empty or `start` is not within \[0, 7\].
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::decode_all_unsigned;
+/// let data = [0b01000110, 0b00000000, 0b11111111, 0b10010101];
+/// assert_eq!(decode_all_unsigned(&data, 0), vec![1, 5, 510, 4]);
+/// ```
+#[cfg(feature = "std")]
+pub fn decode_all_unsigned(buf: &[u8], start: u32) -> Vec<u64> {
+    let mut values = Vec::new();
+    if let Some(mut reader) = ExpGolombDecoder::new(buf, start) {
+        while let Some(value) = reader.next_unsigned() {
+            values.push(value);
+        }
+    }
+    values
+}
+
+/// Decode values written by [`encode_with_zero_runs`](crate::encode_with_zero_runs), expanding the
+/// runs of zeros. Decoding stops at the first codeword that cannot be parsed, and nothing is
+/// returned if `buf` is empty or `start` is not within \[0, 7\].
+///
+/// Since a short codeword can stand for a long run, `None` is returned instead if the result would
+/// have more than `max_values` values or if a value would exceed `u64::MAX`.
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::decode_with_zero_runs;
+/// // 00110 - 5 zeros, 00111 - 7, 1 - no zeros, 1 - 1, 011 - 2 zeros
+/// let data = [0b00110001, 0b11110110];
+/// assert_eq!(decode_with_zero_runs(&data, 0, 9).unwrap(), [0, 0, 0, 0, 0, 7, 1, 0, 0]);
+/// assert_eq!(decode_with_zero_runs(&data, 0, 8), None);
+/// ```
+#[cfg(feature = "std")]
+pub fn decode_with_zero_runs(buf: &[u8], start: u32, max_values: usize) -> Option<Vec<u64>> {
+    let mut values = Vec::new();
+    let Some(mut reader) = ExpGolombDecoder::new(buf, start) else {
+        return Some(values);
+    };
+    while let Some(run) = reader.next_zero_run() {
+        let run = usize::try_from(run).ok()?;
+        if run > max_values - values.len() {
+            return None;
+        }
+        values.resize(values.len() + run, 0);
+
+        let Some(value) = reader.next_unsigned() else {
+            break;
+        };
+        if values.len() == max_values {
+            return None;
+        }
+        values.push(value.checked_add(1)?);
+    }
+    Some(values)
+}
+
+/// Like [`decode_all_unsigned`] but decodes at most `max_values` values. The returned flag is
+/// `true` if decoding stopped because of the limit while more values remained in `buf`, and
+/// `false` if the end of the bitstream was reached.
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::decode_all_unsigned_limited;
+/// let data = [0b01000110, 0b00000000, 0b11111111, 0b10010101];
+/// assert_eq!(decode_all_unsigned_limited(&data, 0, 2), (vec![1, 5], true));
+/// assert_eq!(decode_all_unsigned_limited(&data, 0, 4), (vec![1, 5, 510, 4], false));
+/// assert_eq!(decode_all_unsigned_limited(&data, 0, 100), (vec![1, 5, 510, 4], false));
+/// assert_eq!(decode_all_unsigned_limited(&data, 0, 0), (vec![], true));
+/// ```
+#[cfg(feature = "std")]
+pub fn decode_all_unsigned_limited(buf: &[u8], start: u32, max_values: usize) -> (Vec<u64>, bool) {
+    let mut values = Vec::new();
+    let Some(mut reader) = ExpGolombDecoder::new(buf, start) else {
+        return (values, false);
+    };
+    while values.len() < max_values {
+        match reader.next_unsigned() {
+            Some(value) => values.push(value),
+            None => return (values, false),
+        }
+    }
+    let mut iter = reader.iter;
+    let more = read_unsigned(&mut iter).is_some();
+    (values, more)
+}
+
+/// Decode every unsigned value in `buf` up to the first byte-aligned `0x000001` start code, e.g.
+/// when `buf` runs on into the next NAL unit of an Annex B byte stream. The start code and
+/// everything after it are treated as past the end of the bitstream, and decoding otherwise stops
+/// as in [`decode_all_unsigned`]. Only start codes at byte boundaries are detected since that is
+/// where Annex B places them.
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::decode_until_start_code;
+/// // 010 - 1, 00110 - 5, followed by a start code
+/// let data = [0b01000110, 0x00, 0x00, 0x01, 0b10000000];
+/// assert_eq!(decode_until_start_code(&data, 0), vec![1, 5]);
+/// ```
+#[cfg(feature = "std")]
+pub fn decode_until_start_code(buf: &[u8], start: u32) -> Vec<u64> {
+    let end = buf
+        .windows(3)
+        .position(|bytes| bytes == [0x00, 0x00, 0x01])
+        .unwrap_or(buf.len());
+    let mut values = Vec::new();
+    if let Some(mut reader) = ExpGolombDecoder::new_bounded(buf, start, end * 8) {
+        while let Some(value) = reader.next_unsigned() {
+            values.push(value);
+        }
+    }
+    values
+}
+
+/// Check that `buf` is a sequence of well-formed unsigned Exp-Golomb codewords without decoding
+/// their values, returning the number of complete codewords. A codeword that is too long to be
+/// decoded gives an error of kind [`DecodeErrorKind::Overflow`]. A partial codeword at the end of
+/// `buf`, e.g. the RBSP trailing bits, is ignored if `allow_partial` is `true` and gives
+/// [`DecodeErrorKind::EndOfStream`] otherwise. An empty `buf` or a `start` outside of \[0, 7\]
+/// has no codewords.
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::{validate_unsigned, DecodeErrorKind};
+/// // 010 - 1, 00110 - 5, 1 - 0, 0000000 - partial
+/// let data = [0b01000110, 0b10000000];
+/// assert_eq!(validate_unsigned(&data, 0, true), Ok(3));
+/// let err = validate_unsigned(&data, 0, false).unwrap_err();
+/// assert_eq!((err.kind, err.at_bit), (DecodeErrorKind::EndOfStream, 9));
+/// assert_eq!(validate_unsigned(&data[..1], 0, false), Ok(2));
+///
+/// // 72 leading zeros
+/// let err = validate_unsigned(&[0; 9], 0, true).unwrap_err();
+/// assert_eq!((err.kind, err.at_bit), (DecodeErrorKind::Overflow, 0));
+/// ```
+pub fn validate_unsigned(
+    buf: &[u8],
+    start: u32,
+    allow_partial: bool,
+) -> Result<usize, DecodeError> {
+    let Some(mut reader) = ExpGolombDecoder::new(buf, start) else {
+        return Ok(0);
+    };
+    let mut count = 0;
+    while !reader.iter.is_at_end() {
+        let at_bit = reader.iter.bit_offset();
+        match reader.try_skip_next() {
+            Ok(_) => count += 1,
+            Err(DecodeErrorKind::EndOfStream) if allow_partial => break,
+            Err(kind) => return Err(DecodeError { kind, at_bit }),
+        }
+    }
+    Ok(count)
+}
+
+/// Decode one unsigned value from `buf[byte_range]`, starting at bit `start_bit` of its first
+/// byte. This is for values that are known to sit within a given range of bytes, e.g. a field of
+/// a larger structure, and the value must end within the range. Returns `None` if the range is out
+/// of bounds or empty, or under the same conditions as [`ExpGolombDecoder::new`] and
+/// [`ExpGolombDecoder::next_unsigned`].
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::decode_in_range;
+/// // A header byte followed by 00110 - 5
+/// let data = [0xFF, 0b00110000, 0xFF];
+/// assert_eq!(decode_in_range(&data, 1..2, 0), Some(5));
+/// assert_eq!(decode_in_range(&data, 1..2, 4), None);
+/// assert_eq!(decode_in_range(&data, 2..4, 0), None);
+/// ```
+pub fn decode_in_range(buf: &[u8], byte_range: Range<usize>, start_bit: u32) -> Option<u64> {
+    ExpGolombDecoder::new(buf.get(byte_range)?, start_bit)?.next_unsigned()
+}
+
+/// Decode every unsigned value in `buf` without storing them, returning their sum and count. The
+/// sum wraps around on overflow since this is meant for estimates, e.g. of the bitrate. Decoding
+/// stops under the same conditions as [`decode_all_unsigned`](crate::decode_all_unsigned).
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::decode_stats_unsigned;
+/// let data = [0b01000110, 0b00000000, 0b11111111, 0b10010101];
+/// assert_eq!(decode_stats_unsigned(&data, 0), (520, 4));
+/// ```
+pub fn decode_stats_unsigned(buf: &[u8], start: u32) -> (u64, usize) {
+    let mut sum = 0u64;
+    let mut count = 0;
+    if let Some(mut reader) = ExpGolombDecoder::new(buf, start) {
+        while let Some(value) = reader.next_unsigned() {
+            sum = sum.wrapping_add(value);
+            count += 1;
+        }
+    }
+    (sum, count)
+}
+
+/// Decode every signed value in `buf` without storing them, returning their sum and count. The sum
+/// is an `i128` so it only overflows after more than 2^64 values.
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::decode_stats_signed;
+/// let data = [0b10100110, 0b01000010, 0b10011000, 0b11100010, 0b00000100, 0b10000000];
+/// assert_eq!(decode_stats_signed(&data, 0), (0, 9));
+/// ```
+pub fn decode_stats_signed(buf: &[u8], start: u32) -> (i128, usize) {
+    let mut sum = 0i128;
+    let mut count = 0;
+    if let Some(mut reader) = ExpGolombDecoder::new(buf, start) {
+        while let Some(value) = reader.next_signed() {
+            sum = sum.wrapping_add(value as i128);
+            count += 1;
+        }
+    }
+    (sum, count)
+}
+
+/// Statistics about the unsigned values of a bitstream, as returned by [`analyze_unsigned`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StreamStats {
+    /// The number of values decoded.
+    pub count: usize,
+    /// The total length of their codewords in bits.
+    pub total_bits: usize,
+    /// The smallest value, or `None` if there were no values.
+    pub min: Option<u64>,
+    /// The largest value, or `None` if there were no values.
+    pub max: Option<u64>,
+    /// The number of codewords for each number of leading zeros.
+    pub prefix_lengths: [usize; 65],
+}
+
+/// Decode every unsigned value in `buf` in a single pass, collecting [`StreamStats`] about them,
+/// e.g. to judge how well Exp-Golomb coding suits the data. Decoding stops under the same
+/// conditions as [`decode_all_unsigned`](crate::decode_all_unsigned).
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::analyze_unsigned;
+/// // 010 - 1, 00110 - 5, 00000000111111111 - 510, 00101 - 4
+/// let data = [0b01000110, 0b00000000, 0b11111111, 0b10010101];
+/// let stats = analyze_unsigned(&data, 0);
+/// assert_eq!(stats.count, 4);
+/// assert_eq!(stats.total_bits, 30);
+/// assert_eq!((stats.min, stats.max), (Some(1), Some(510)));
+/// assert_eq!(stats.prefix_lengths[..9], [0, 1, 2, 0, 0, 0, 0, 0, 1]);
+/// ```
+pub fn analyze_unsigned(buf: &[u8], start: u32) -> StreamStats {
+    let mut stats = StreamStats {
+        count: 0,
+        total_bits: 0,
+        min: None,
+        max: None,
+        prefix_lengths: [0; 65],
+    };
+    if let Some(mut reader) = ExpGolombDecoder::new(buf, start) {
+        while let Some((value, lz)) = reader.next_unsigned_with_prefix() {
+            stats.count += 1;
+            stats.total_bits += 2 * lz as usize + 1;
+            stats.min = Some(stats.min.map_or(value, |min| min.min(value)));
+            stats.max = Some(stats.max.map_or(value, |max| max.max(value)));
+            stats.prefix_lengths[lz as usize] += 1;
+        }
+    }
+    stats
+}
+
+#[cfg_attr(feature = "inline-always", inline(always))]
+#[cfg_attr(not(feature = "inline-always"), inline)]
+pub(crate) fn count_leading_zeroes<S: BitSource + ?Sized>(
+    source: &mut S,
+    max_leading_zeros: u32,
+) -> Option<u32> {
+    try_count_leading_zeroes(source, max_leading_zeros).ok()
+}
+
+#[cfg_attr(feature = "inline-always", inline(always))]
+#[cfg_attr(not(feature = "inline-always"), inline)]
+fn try_count_leading_zeroes<S: BitSource + ?Sized>(
+    source: &mut S,
+    max_leading_zeros: u32,
+) -> Result<u32, DecodeErrorKind> {
+    let mut leading_zeros = 0;
+    while let Some(bit) = source.next_bit() {
+        if bit == 0 {
+            leading_zeros += 1;
+            if leading_zeros > max_leading_zeros {
+                return Err(DecodeErrorKind::Overflow);
+            }
+        } else {
+            return Ok(leading_zeros);
+        }
+    }
+    Err(DecodeErrorKind::EndOfStream)
+}
+
+/// 32-bit counterpart of [`read_unsigned`].
+#[inline]
+fn read_unsigned_small<S: BitSource + ?Sized>(
+    source: &mut S,
+    max_leading_zeros: u32,
+) -> Option<u32> {
+    let lz = count_leading_zeroes(source, max_leading_zeros.min(u32::BITS))?;
+    let x = 1u32.checked_shl(lz).map_or(u32::MAX, |v| v - 1);
+    let mut y = 0u32;
+    for _ in 0..lz {
+        y = (y << 1) | source.next_bit()? as u32;
+    }
+    x.checked_add(y)
+}
+
+#[inline]
+pub(crate) fn read_unsigned<S: BitSource + ?Sized>(source: &mut S) -> Option<u64> {
+    read_unsigned_with_prefix(source, u64::BITS).map(|(value, _)| value)
+}
+
+#[inline]
+pub(crate) fn read_unsigned_with_prefix<S: BitSource + ?Sized>(
+    source: &mut S,
+    max_leading_zeros: u32,
+) -> Option<(u64, u32)> {
+    try_read_unsigned_with_prefix(source, max_leading_zeros).ok()
+}
+
+#[inline]
+fn try_read_unsigned_with_prefix<S: BitSource + ?Sized>(
+    source: &mut S,
+    max_leading_zeros: u32,
+) -> Result<(u64, u32), DecodeErrorKind> {
+    let lz = try_count_leading_zeroes(source, max_leading_zeros)?;
+    debug_assert!(lz <= u64::BITS);
+    if lz == u64::BITS {
+        // `2^64 - 1` is `u64::MAX` so only an all-zero suffix is representable. The whole suffix is
+        // still consumed before reporting an overflow.
+        let mut overflow = false;
+        for _ in 0..lz {
+            overflow |= source.next_bit().ok_or(DecodeErrorKind::EndOfStream)? != 0;
+        }
+        return if overflow {
+            Err(DecodeErrorKind::Overflow)
+        } else {
+            Ok((u64::MAX, lz))
+        };
+    }
+
+    let x = (1u64 << lz) - 1;
+    let mut y = 0;
+    let mut remaining = lz;
+
+    if remaining != 0 {
+        while let Some(bit) = source.next_bit() {
+            y <<= 1;
+            y |= bit as u64;
+            remaining -= 1;
+            if remaining == 0 {
+                break;
+            }
+        }
+        if remaining != 0 {
+            return Err(DecodeErrorKind::EndOfStream);
+        }
+    }
+    let value = x.checked_add(y).ok_or(DecodeErrorKind::Overflow)?;
+    Ok((value, lz))
+}
+
+/// Returns `2^frac_bits` as an `f64`, or `None` if `frac_bits` is more than 64.
+#[inline]
+fn fixed_point_scale(frac_bits: u32) -> Option<f64> {
+    if frac_bits > u64::BITS {
+        return None;
+    }
+    Some((1u128 << frac_bits) as f64)
+}
+
+/// Returns the signed value that [`ExpGolombDecoder::next_signed`] reads for the code number `k`.
+/// Odd numbers map to positive values and even numbers to the rest, so `u64::MAX` maps to
+/// `i64::MIN`. This is the inverse of [`signed_to_code`](crate::signed_to_code).
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::code_to_signed;
+/// assert_eq!(code_to_signed(0), 0);
+/// assert_eq!(code_to_signed(1), 1);
+/// assert_eq!(code_to_signed(2), -1);
+/// assert_eq!(code_to_signed(u64::MAX), i64::MIN);
+/// ```
+#[inline]
+pub fn code_to_signed(k: u64) -> i64 {
+    unsigned_to_signed(k)
+}
+
+#[inline]
+pub(crate) fn unsigned_to_signed(k: u64) -> i64 {
+    let factor = if k.is_multiple_of(2) { -1 } else { 1 };
+    factor * (k / 2 + k % 2) as i64
+}
+
+/// Maps odd code numbers to negative values and even ones to non-negative values. `u64::MAX` is
+/// read as `i64::MIN`.
+#[inline]
+pub(crate) fn unsigned_to_signed_neg_first(k: u64) -> i64 {
+    let half = (k / 2) as i64;
+    if k.is_multiple_of(2) {
+        half
+    } else {
+        -half - 1
+    }
+}
+
+/// Lookup table of `(value, codeword length)` indexed by a byte whose leading bits form a complete
+/// codeword. Bytes where the codeword does not end within the byte map to `(0, 0)`.
+const SMALL_VALUES: [(u8, u8); 256] = {
+    let mut table = [(0, 0); 256];
+    let mut byte = 1;
+    while byte < 256 {
+        let len = 2 * (byte as u8).leading_zeros() + 1;
+        if len <= 8 {
+            table[byte] = (((byte >> (8 - len)) - 1) as u8, len as u8);
+        }
+        byte += 1;
+    }
+    table
+};
+
+struct BitIterator<'a> {
+    buf: &'a [u8],
+    index: usize,
+    bit_pos: u32,
+    start_index: usize,
+    start_bit_pos: u32,
+    end_index: usize,
+    end_bit_pos: u32,
+    observer: Option<&'a dyn Fn(u8)>,
+}
+
+impl<'a> BitIterator<'a> {
+    #[inline]
+    const fn new(buf: &'a [u8], shift_sub: u32) -> BitIterator<'a> {
+        Self {
+            buf,
+            index: 0,
+            bit_pos: shift_sub,
+            start_index: 0,
+            start_bit_pos: shift_sub,
+            end_index: buf.len(),
+            end_bit_pos: 0,
+            observer: None,
+        }
+    }
+
+    /// Returns a copy of the iterator without the observer, e.g. to look ahead.
+    #[inline]
+    fn detached(&self) -> BitIterator<'a> {
+        BitIterator {
+            buf: self.buf,
+            index: self.index,
+            bit_pos: self.bit_pos,
+            start_index: self.start_index,
+            start_bit_pos: self.start_bit_pos,
+            end_index: self.end_index,
+            end_bit_pos: self.end_bit_pos,
+            observer: None,
+        }
+    }
+
+    /// Returns the number of bits between the current position and the end.
+    #[inline]
+    fn remaining_bits(&self) -> usize {
+        (self.end_index - self.index) * 8 + self.end_bit_pos as usize - self.bit_pos as usize
+    }
+
+    /// Returns the number of bits from the start of `buf` to the current position.
     #[inline]
-    fn new(buf: &'a [u8], shift_sub: u32) -> BitIterator<'a> {
-        Self {
-            buf,
-            index: 0,
-            bit_pos: shift_sub,
+    fn bit_offset(&self) -> usize {
+        self.index * 8 + self.bit_pos as usize
+    }
+
+    #[inline]
+    fn is_at_end(&self) -> bool {
+        (self.index, self.bit_pos) >= (self.end_index, self.end_bit_pos)
+    }
+
+    /// Returns the position `num_bits` after the current one or `None` if it is past the end.
+    #[inline]
+    fn offset_position(&self, num_bits: usize) -> Option<(usize, u32)> {
+        let offset = (self.bit_pos as usize).checked_add(num_bits)?;
+        let pos = (self.index.checked_add(offset / 8)?, (offset % 8) as u32);
+        if pos > (self.end_index, self.end_bit_pos) {
+            None
+        } else {
+            Some(pos)
+        }
+    }
+
+    /// Returns the unread bits of the current byte shifted to the top along with how many of them
+    /// there are before the end.
+    #[inline]
+    fn current_bits(&self) -> Option<(u8, u32)> {
+        let available = if self.index < self.end_index {
+            8 - self.bit_pos
+        } else if self.bit_pos < self.end_bit_pos {
+            self.end_bit_pos - self.bit_pos
+        } else {
+            return None;
+        };
+        Some((self.buf[self.index] << self.bit_pos, available))
+    }
+
+    /// Returns the position of the last `1` bit between the current position and the end.
+    #[inline]
+    fn last_set_bit(&self) -> Option<(usize, u32)> {
+        let mut index = self.end_index + (self.end_bit_pos != 0) as usize;
+        while index > self.index {
+            index -= 1;
+            let mut byte = self.buf[index];
+            if index == self.end_index {
+                byte &= !(0xFF >> self.end_bit_pos);
+            }
+            if index == self.index {
+                byte &= 0xFF >> self.bit_pos;
+            }
+            if byte != 0 {
+                return Some((index, 7 - byte.trailing_zeros()));
+            }
+        }
+        None
+    }
+
+    /// Advances by `num_bits` that are known to be available in the current byte.
+    #[inline]
+    fn advance_within_byte(&mut self, num_bits: u32) {
+        let bit_pos = self.bit_pos + num_bits;
+        debug_assert!(bit_pos <= 8);
+        self.advance_to((self.index + (bit_pos / 8) as usize, bit_pos % 8));
+    }
+
+    #[inline]
+    fn move_to_end(&mut self) {
+        self.advance_to((self.end_index, self.end_bit_pos));
+    }
+
+    /// Moves forward to `pos`, passing the bits in between to the observer if there is one.
+    #[inline]
+    fn advance_to(&mut self, pos: (usize, u32)) {
+        if self.observer.is_some() {
+            while (self.index, self.bit_pos) < pos && self.next().is_some() {}
+            return;
         }
+        (self.index, self.bit_pos) = pos;
     }
 
+    /// Advances by `num_bits`, returning `None` if the end was reached before that.
     #[inline]
-    fn skip_bits(&mut self, num_bits: u32) {
-        let offset = self.bit_pos as usize + num_bits as usize;
-        self.index = usize::min(self.buf.len(), self.index + offset / 8);
-        self.bit_pos = (offset % 8) as u32;
+    fn skip_bits(&mut self, num_bits: u32) -> Option<()> {
+        match self.offset_position(num_bits as usize) {
+            Some(pos) => {
+                self.advance_to(pos);
+                Some(())
+            }
+            None => {
+                self.move_to_end();
+                None
+            }
+        }
     }
 }
 
 impl<'a> core::iter::Iterator for BitIterator<'a> {
     type Item = u8;
 
-    #[inline]
+    #[cfg_attr(feature = "inline-always", inline(always))]
+    #[cfg_attr(not(feature = "inline-always"), inline)]
     fn next(&mut self) -> Option<Self::Item> {
-        let curr_byte = *self.buf.get(self.index)?;
+        // Comparing against the end position instead of relying on `buf.get` also prevents
+        // wrap-around to a valid index which will make this function return `Some` after
+        // signaling `None`
+        if self.is_at_end() {
+            return None;
+        }
+        let curr_byte = self.buf[self.index];
         let shift = 7 - self.bit_pos;
-        let bit = curr_byte & (1 << shift);
+        let bit = (curr_byte >> shift) & 1;
 
         self.bit_pos += 1;
         if self.bit_pos == 8 {
             self.bit_pos = 0;
-            // Increment only when the index has not reached the end of the buffer to prevent
-            // wrap-around to a valid index which will make this function return `Some` after
-            // signaling `None`
-            if self.index < self.buf.len() {
-                self.index += 1;
-            }
+            self.index += 1;
+        }
+
+        if let Some(observer) = self.observer {
+            observer(bit);
         }
+        Some(bit)
+    }
+}
+
+/// An iterator over the bits of an [`ExpGolombDecoder`], created by
+/// [`ExpGolombDecoder::as_bit_reader`].
+pub struct BitReaderRef<'r, 'a> {
+    iter: &'r mut BitIterator<'a>,
+}
+
+impl<'r, 'a> core::iter::Iterator for BitReaderRef<'r, 'a> {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.iter.remaining_bits();
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'r, 'a> ExactSizeIterator for BitReaderRef<'r, 'a> {}
+
+/// An iterator over the unsigned values of an [`ExpGolombDecoder`], created by
+/// [`ExpGolombDecoder::unsigned_iter`].
+pub struct UnsignedIter<'r, 'a> {
+    decoder: &'r mut ExpGolombDecoder<'a>,
+}
+
+impl<'r, 'a> core::iter::Iterator for UnsignedIter<'r, 'a> {
+    type Item = u64;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.decoder.next_unsigned()
+    }
+
+    /// The smallest codeword is a single bit, so there can be at most as many values as there
+    /// are bits left.
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.decoder.remaining_bits()))
+    }
+}
+
+impl<'r, 'a> BitSource for BitReaderRef<'r, 'a> {
+    #[inline]
+    fn next_bit(&mut self) -> Option<u8> {
+        self.iter.next()
+    }
+}
+
+impl<'a> BitSource for ExpGolombDecoder<'a> {
+    #[inline]
+    fn next_bit(&mut self) -> Option<u8> {
+        self.iter.next()
+    }
+}
 
-        Some(bit >> shift)
+impl<'a> BitSource for BitIterator<'a> {
+    #[inline]
+    fn next_bit(&mut self) -> Option<u8> {
+        self.next()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util;
+    use core::cell::Cell;
 
     #[test]
     fn empty_buffer() {
@@ -283,6 +2574,73 @@ mod tests {
             assert!(ExpGolombDecoder::new(&data, i).is_some());
         }
         assert!(ExpGolombDecoder::new(&data, 8).is_none());
+
+        // `start` is within the first byte, so the last valid one reads its last bit
+        let mut reader = ExpGolombDecoder::new(&[0b00000001], 7).unwrap();
+        assert_eq!(reader.next_bit(), Some(1));
+        assert_eq!(reader.next_bit(), None);
+        for bit_offset in 0..=8 {
+            let reader = ExpGolombDecoder::new_at_bit(&data, bit_offset).unwrap();
+            assert_eq!(reader.remaining_bits(), 8 - bit_offset);
+        }
+        assert!(ExpGolombDecoder::new_at_bit(&data, 9).is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn decode_fields_rejects_values_beyond_i64() {
+        let data = [0xFF; 8];
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        let mut out = Vec::new();
+        assert!(reader
+            .decode_fields(&[FieldSpec::Fixed(63), FieldSpec::Fixed(1)], &mut out)
+            .is_some());
+        assert_eq!(out, [i64::MAX, 1]);
+
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        assert!(reader
+            .decode_fields(&[FieldSpec::Fixed(64)], &mut out)
+            .is_none());
+        assert_eq!(out.len(), 2);
+    }
+
+    #[test]
+    fn small_codewords_cut_by_end_bit() {
+        // 010 - 1, 1 - 0
+        let data = [0b01010000];
+        for end_bit in 0..8 {
+            let mut reader = ExpGolombDecoder::new_bounded(&data, 0, end_bit).unwrap();
+            let mut small = ExpGolombDecoder::new_bounded(&data, 0, end_bit).unwrap();
+            let expected: &[u64] = match end_bit {
+                0..=2 => &[],
+                3 => &[1],
+                _ => &[1, 0],
+            };
+            for &value in expected {
+                assert_eq!(reader.next_unsigned(), Some(value));
+                assert_eq!(small.next_unsigned_small(), Some(value as u32));
+            }
+            assert_eq!(reader.next_unsigned(), None);
+            assert_eq!(small.next_unsigned_small(), None);
+        }
+    }
+
+    #[test]
+    fn start_code_search_bounds() {
+        let data = [0x00, 0x00, 0x01, 0x00, 0x00, 0x01];
+
+        // A start code at the current byte is only found when the decoder is at its start
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        assert_eq!(reader.seek_next_start_code(), Some(()));
+        assert_eq!(reader.position(), BitPosition::new(3, 0));
+        let mut reader = ExpGolombDecoder::new(&data, 1).unwrap();
+        assert_eq!(reader.seek_next_start_code(), Some(()));
+        assert_eq!(reader.position(), BitPosition::new(6, 0));
+
+        // Bytes past the end of a bounded decoder are not searched
+        let mut reader = ExpGolombDecoder::new_bounded(&data, 1, 47).unwrap();
+        assert_eq!(reader.seek_next_start_code(), None);
+        assert_eq!(reader.position(), BitPosition::new(0, 1));
     }
 
     #[test]
@@ -315,4 +2673,535 @@ mod tests {
         assert_eq!(reader.next_unsigned(), Some(1));
         assert_eq!(reader.next_bit(), Some(1));
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn next_unsigned_vec_rejects_oversized_count() {
+        // 0000001100101 - 100, followed by only 11 bits
+        let data = [0b00000011, 0b00101111, 0b11111111];
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        assert_eq!(reader.next_unsigned_vec(), None);
+        assert_eq!(reader.position(), BitPosition::new(data.len(), 0));
+
+        // 0001100 - 11, followed by 11 ones
+        let data = [0b00011001, 0b11111111, 0b11000000];
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        assert_eq!(reader.next_unsigned_vec(), Some(vec![0; 11]));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn decode_until_start_code_edges() {
+        // Start code bits that are not byte-aligned are decoded as usual: 1 - 0, followed by
+        // 00000000 00000000 0000001 which is 23 bits of an incomplete codeword
+        let data = [0b10000000, 0x00, 0x00, 0b00000010];
+        assert_eq!(
+            decode_until_start_code(&data, 0),
+            decode_all_unsigned(&data, 0)
+        );
+
+        // A start code right at the beginning
+        let data = [0x00, 0x00, 0x01, 0b10000000];
+        assert_eq!(decode_until_start_code(&data, 0), vec![]);
+        assert_eq!(decode_until_start_code(&data, 3), vec![]);
+
+        // 1 - 0, then 00000000111111100 - 507 is cut short by the start code
+        let data = [0b10000000, 0b01111111, 0x00, 0x00, 0x01];
+        assert_eq!(decode_until_start_code(&data, 0), vec![0]);
+        assert_eq!(decode_all_unsigned(&data, 0), vec![0, 507]);
+    }
+
+    #[test]
+    fn split_at_every_bit() {
+        let data = [0b01010101, 0b11001100];
+        for start in 0..=7 {
+            for num_bits in 0..=(16 - start as usize) {
+                let reader = ExpGolombDecoder::new(&data, start).unwrap();
+                let (mut first, mut second) = reader.split_at_bit(num_bits).unwrap();
+
+                let mut reader = ExpGolombDecoder::new(&data, start).unwrap();
+                for _ in 0..num_bits {
+                    assert_eq!(first.next_bit(), reader.next_bit());
+                }
+                assert_eq!(first.next_bit(), None);
+                while let Some(bit) = reader.next_bit() {
+                    assert_eq!(second.next_bit(), Some(bit));
+                }
+                assert_eq!(second.next_bit(), None);
+            }
+            let reader = ExpGolombDecoder::new(&data, start).unwrap();
+            assert!(reader.split_at_bit(17 - start as usize).is_none());
+        }
+    }
+
+    #[test]
+    fn seek_stays_within_bounds() {
+        let data = [0b01000110, 0b00101011];
+        let at = |bit: usize| BitPosition::new(bit / 8, (bit % 8) as u8);
+        for start in 0..=7 {
+            let mut reader = ExpGolombDecoder::new(&data, start).unwrap();
+            for bit in 0..start as usize {
+                assert!(reader.seek(at(bit)).is_none());
+                assert!(reader.clone_at(0, bit as u32).is_none());
+            }
+            assert_eq!(reader.position(), at(start as usize));
+            assert!(reader.seek(at(start as usize)).is_some());
+
+            let (mut first, mut second) = reader.split_at_bit(3).unwrap();
+            let split = start as usize + 3;
+            for bit in 0..=16 {
+                let in_first = (start as usize..=split).contains(&bit);
+                assert_eq!(first.seek(at(bit)).is_some(), in_first);
+                assert_eq!(second.seek(at(bit)).is_some(), bit >= split);
+                assert_eq!(
+                    second.clone_at(bit / 8, bit as u32 % 8).is_some(),
+                    bit >= split
+                );
+            }
+        }
+
+        let mut reader = ExpGolombDecoder::new_at_bit(&data, 5).unwrap();
+        assert!(reader.seek(at(4)).is_none());
+        assert!(reader.seek(at(5)).is_some());
+    }
+
+    #[test]
+    fn more_rbsp_data_matches_bit_loop() {
+        let data = [0b00010010, 0b00000000, 0b01000000];
+        for start in 0..=7 {
+            for end_bit in start as usize..=24 {
+                let mut reader = ExpGolombDecoder::new_bounded(&data, start, end_bit).unwrap();
+                loop {
+                    let mut bits = ExpGolombDecoder::new_bounded(&data, start, end_bit).unwrap();
+                    bits.seek(reader.position()).unwrap();
+                    let last = bits
+                        .as_bit_reader()
+                        .enumerate()
+                        .filter(|&(_, bit)| bit == 1)
+                        .last();
+                    let expected = matches!(last, Some((i, _)) if i > 0);
+                    assert_eq!(reader.more_rbsp_data(), expected);
+                    if reader.next_bit().is_none() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn remaining_bytes_at_end() {
+        let data = [0b10000000];
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        assert_eq!(reader.remaining_bytes(), Some(&data[..]));
+        for _ in 0..8 {
+            reader.next_bit();
+        }
+        assert_eq!(reader.remaining_bytes(), Some(&[][..]));
+
+        let reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        let (first, second) = reader.split_at_bit(4).unwrap();
+        assert_eq!(first.remaining_bytes(), None);
+        assert_eq!(second.remaining_bytes(), None);
+    }
+
+    #[test]
+    fn end_bit_validity() {
+        let data = [0b01000000, 0b00000000];
+        for start in 0..=7 {
+            if let Some(end_bit) = (start as usize).checked_sub(1) {
+                assert!(ExpGolombDecoder::new_bounded(&data, start, end_bit).is_none());
+            }
+            for end_bit in start as usize..=16 {
+                let mut reader = ExpGolombDecoder::new_bounded(&data, start, end_bit).unwrap();
+                for _ in start as usize..end_bit {
+                    assert!(reader.next_bit().is_some());
+                }
+                assert_eq!(reader.next_bit(), None);
+            }
+            assert!(ExpGolombDecoder::new_bounded(&data, start, 17).is_none());
+        }
+    }
+
+    #[test]
+    fn position_after_failed_read() {
+        // 65 leading zeros
+        let data = [0, 0, 0, 0, 0, 0, 0, 0, 0b00000000, 0b10000000];
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        assert_eq!(reader.next_unsigned(), None);
+        assert_eq!(reader.position(), BitPosition::new(data.len(), 0));
+        assert_eq!(reader.next_bit(), None);
+
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        reader.skip_next();
+        assert_eq!(reader.position(), BitPosition::new(data.len(), 0));
+
+        // Truncated suffix
+        let data = [0b00000100];
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        assert_eq!(reader.next_signed(), None);
+        assert_eq!(reader.position(), BitPosition::new(data.len(), 0));
+
+        let mut reader = ExpGolombDecoder::new_bounded(&data, 0, 6).unwrap();
+        assert_eq!(reader.next_unsigned(), None);
+        assert_eq!(reader.position(), BitPosition::new(0, 6));
+    }
+
+    #[test]
+    fn signed_fixed_width_edges() {
+        let data = [0b10000000, 0, 0, 0, 0, 0, 0, 0, 0b01000000];
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        assert_eq!(reader.next_signed_fixed(64), Some(i64::MIN));
+        assert_eq!(reader.next_signed_fixed(1), Some(0));
+        assert_eq!(reader.next_signed_fixed(1), Some(-1));
+
+        let data = [0xFF; 9];
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        assert_eq!(reader.next_signed_fixed(64), Some(-1));
+        assert_eq!(reader.next_signed_fixed(1), Some(-1));
+        assert_eq!(reader.next_signed_fixed(65), None);
+
+        let data = [0x7F, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        assert_eq!(reader.next_signed_fixed(64), Some(i64::MAX));
+
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        assert_eq!(reader.next_signed_fixed(0), None);
+    }
+
+    #[test]
+    fn longest_prefixes() {
+        // 63 leading zeros followed by `1` and 63 ones
+        let mut data = [0u8; 16];
+        data[7] = 0b00000001;
+        data[8..].fill(0b11111111);
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        assert_eq!(reader.next_unsigned_with_prefix(), Some((u64::MAX - 1, 63)));
+        assert_eq!(reader.position(), BitPosition::new(15, 7));
+
+        // 64 leading zeros followed by `1` and 64 zeros
+        let mut data = [0u8; 17];
+        data[8] = 0b10000000;
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        assert_eq!(reader.next_unsigned_with_prefix(), Some((u64::MAX, 64)));
+        assert_eq!(reader.position(), BitPosition::new(16, 1));
+
+        // 64 leading zeros followed by `1` and a non-zero suffix
+        data[16] = 0b10000000;
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        assert_eq!(reader.next_unsigned(), None);
+        assert_eq!(reader.position(), BitPosition::new(data.len(), 0));
+
+        // 65 leading zeros
+        let mut data = [0u8; 18];
+        data[8] = 0b01000000;
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        assert_eq!(reader.next_unsigned(), None);
+        assert_eq!(reader.position(), BitPosition::new(data.len(), 0));
+    }
+
+    #[test]
+    fn bit_observer_sees_every_consumed_bit() {
+        let data = [0b10011000, 0b00000011, 0b11111110, 0b01000000];
+        let bits = [const { Cell::new(0u8) }; 32];
+        let num_bits = Cell::new(0);
+        let record = |bit| {
+            bits[num_bits.get()].set(bit);
+            num_bits.set(num_bits.get() + 1);
+        };
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        reader.set_bit_observer(&record);
+        // The first two codewords take the byte-aligned fast path
+        assert_eq!(reader.next_unsigned(), Some(0));
+        assert_eq!(reader.next_unsigned(), Some(5));
+        reader.skip_next();
+        assert_eq!(reader.next_bit(), Some(0));
+        assert_eq!(reader.next_n_bits(2), Some(0b01));
+        // Runs out of bits, moving to the end
+        assert_eq!(reader.next_unsigned(), None);
+
+        assert_eq!(num_bits.get(), 32);
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        assert!(reader.as_bit_reader().eq(bits.iter().map(Cell::get)));
+    }
+
+    #[test]
+    fn decoder_is_covariant() {
+        // Only needs to compile, which it would not if the decoder were invariant over `'a`
+        fn shorten<'s>(reader: ExpGolombDecoder<'static>) -> ExpGolombDecoder<'s> {
+            reader
+        }
+        static DATA: [u8; 1] = [0b01000000];
+        let observer = |_| {};
+        let mut reader = shorten(ExpGolombDecoder::new(&DATA, 0).unwrap());
+        reader.set_bit_observer(&observer);
+        assert_eq!(reader.next_unsigned(), Some(1));
+    }
+
+    #[test]
+    fn u64_max_boundary() {
+        // 64 leading zeros followed by `1` and 64 zeros - u64::MAX
+        let mut data = [0u8; 17];
+        data[8] = 0b10000000;
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        assert_eq!(reader.try_next_unsigned(), Ok(u64::MAX));
+
+        // Any set bit in the suffix is at least u64::MAX + 1
+        for bit in 65..129 {
+            let mut data = data;
+            data[bit / 8] |= 0b10000000 >> (bit % 8);
+            let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+            let err = reader.try_next_unsigned().unwrap_err();
+            assert_eq!(err.kind, DecodeErrorKind::Overflow);
+
+            let mut iter = BitIterator::new(&data, 0);
+            assert_eq!(
+                try_read_unsigned_with_prefix(&mut iter, u64::BITS),
+                Err(DecodeErrorKind::Overflow)
+            );
+            assert_eq!(iter.bit_offset(), 129);
+        }
+
+        // Truncated suffix
+        let mut reader = ExpGolombDecoder::new(&data[..16], 0).unwrap();
+        let err = reader.try_next_unsigned().unwrap_err();
+        assert_eq!(err.kind, DecodeErrorKind::EndOfStream);
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn positions_past_u32_max() {
+        // Only the position arithmetic is exercised so the bytes themselves are never read
+        let boundary = u32::MAX as usize;
+        let len = boundary + 2;
+        let mut iter = BitIterator {
+            buf: &[],
+            index: boundary,
+            bit_pos: 6,
+            start_index: 0,
+            start_bit_pos: 0,
+            end_index: len,
+            end_bit_pos: 0,
+            observer: None,
+        };
+
+        assert_eq!(iter.skip_bits(3), Some(()));
+        assert_eq!((iter.index, iter.bit_pos), (boundary + 1, 1));
+        assert_eq!(iter.bit_offset(), (boundary + 1) * 8 + 1);
+        assert_eq!(iter.offset_position(7), Some((len, 0)));
+        assert_eq!(iter.offset_position(8), None);
+        assert_eq!(iter.offset_position(usize::MAX), None);
+
+        let reader = ExpGolombDecoder {
+            iter,
+            max_leading_zeros: u64::BITS,
+        };
+        assert_eq!(reader.position(), BitPosition::new(boundary + 1, 1));
+        let (first, second) = reader.split_at_bit(4).unwrap();
+        assert_eq!(
+            (first.iter.end_index, first.iter.end_bit_pos),
+            (boundary + 1, 5)
+        );
+        assert_eq!(second.position(), BitPosition::new(boundary + 1, 5));
+
+        let mut iter = second.iter;
+        assert_eq!(iter.skip_bits(64), None);
+        assert_eq!((iter.index, iter.bit_pos), (len, 0));
+        assert!(iter.is_at_end());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn next_unsigned_small_matches_next_unsigned() {
+        let mut values: Vec<u64> = (0..1024).collect();
+        for shift in 10..=32 {
+            values.extend([(1 << shift) - 2, (1 << shift) - 1]);
+        }
+        values.extend((0..u32::MAX as u64).step_by(104_729));
+        // Moves the reader to the end so it has to be last
+        values.push(u32::MAX as u64 + 1);
+
+        for start in 0..=7 {
+            let mut buf = vec![0u8; crate::unsigned_sequence_len_bits(&values).div_ceil(8) + 1];
+            let mut writer = crate::ExpGolombEncoder::new(&mut buf, start).unwrap();
+            for &value in &values {
+                writer.put_unsigned(value).unwrap();
+            }
+            writer.close();
+
+            let mut reader = ExpGolombDecoder::new(&buf, start).unwrap();
+            let mut small_reader = ExpGolombDecoder::new(&buf, start).unwrap();
+            for &value in &values {
+                assert_eq!(reader.next_unsigned(), Some(value));
+                let expected = u32::try_from(value).ok();
+                assert_eq!(small_reader.next_unsigned_small(), expected);
+                if expected.is_some() {
+                    assert_eq!(small_reader.position(), reader.position());
+                } else {
+                    assert_eq!(small_reader.position(), BitPosition::new(buf.len(), 0));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn next_unsigned_raw_longest_codeword() {
+        // 64 leading zeros followed by `1` and 64 zeros
+        let mut data = [0u8; 17];
+        data[8] = 0b10000000;
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        assert_eq!(reader.next_unsigned_raw(), Some((u64::MAX, 1 << 64, 129)));
+    }
+
+    #[test]
+    fn from_position_resumes_at_every_bit() {
+        let data = test_util::DATA;
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        loop {
+            let pos = reader.position();
+            let mut resumed = ExpGolombDecoder::from_position(&data, pos).unwrap();
+            assert_eq!(resumed.position(), pos);
+            let mut copy = ExpGolombDecoder {
+                iter: reader.iter.detached(),
+                ..reader
+            };
+            assert_eq!(resumed.next_unsigned(), copy.next_unsigned());
+            if reader.next_bit().is_none() {
+                break;
+            }
+        }
+        assert!(ExpGolombDecoder::from_position(&[], BitPosition::new(0, 0)).is_some());
+        let past_end = BitPosition::new(data.len() + 1, 0);
+        assert!(ExpGolombDecoder::from_position(&data, past_end).is_none());
+    }
+
+    #[test]
+    fn max_codeword_bits_stops_early() {
+        // 50 leading zeros followed by `1` and 50 zeros
+        let mut data = [0u8; 13];
+        data[6] = 0b00100000;
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        assert_eq!(reader.next_unsigned(), Some((1 << 50) - 1));
+        let reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        let mut limited = reader.max_codeword_bits(101);
+        assert_eq!(limited.next_unsigned(), Some((1 << 50) - 1));
+        let reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        let mut limited = reader.max_codeword_bits(100);
+        assert_eq!(limited.next_unsigned(), None);
+
+        // Only the zeros up to the limit are read from a 100-bit prefix
+        let data = [0u8; 13];
+        let limited = ExpGolombDecoder::new(&data, 0)
+            .unwrap()
+            .max_codeword_bits(40);
+        let mut iter = limited.iter;
+        assert_eq!(
+            count_leading_zeroes(&mut iter, limited.max_leading_zeros),
+            None
+        );
+        assert_eq!((iter.index, iter.bit_pos), (2, 4));
+
+        // The lookup table must not bypass the limit
+        let data = [0b00110000];
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        assert_eq!(reader.next_unsigned(), Some(5));
+        let mut limited = ExpGolombDecoder::new(&data, 0)
+            .unwrap()
+            .max_codeword_bits(4);
+        assert_eq!(limited.next_unsigned(), None);
+        let mut limited = ExpGolombDecoder::new(&data, 0)
+            .unwrap()
+            .max_codeword_bits(4);
+        assert_eq!(limited.next_unsigned_small(), None);
+    }
+
+    #[test]
+    fn try_unsigned_iter_is_fused_after_overflow() {
+        // 00110 - 5, 0001000 - 7 (too long for the limit), 1 - 0
+        let data = [0b00110000, 0b10001000];
+        let mut reader = ExpGolombDecoder::new(&data, 0)
+            .unwrap()
+            .max_codeword_bits(5);
+        let mut iter = reader.try_unsigned_iter();
+        assert_eq!(iter.next(), Some(Ok(5)));
+        let overflow = DecodeError {
+            kind: DecodeErrorKind::Overflow,
+            at_bit: 5,
+        };
+        assert_eq!(iter.next(), Some(Err(overflow)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn unsigned_iter_flagged_edges() {
+        // 1 - 0
+        let data = [0b10000000];
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        let mut iter = reader.unsigned_iter_flagged();
+        assert_eq!(iter.next(), Some((0, true)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+
+        let data = [0b00000000];
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        assert_eq!(reader.unsigned_iter_flagged().next(), None);
+
+        // 1 - 0 at the very end of the buffer
+        let mut reader = ExpGolombDecoder::new(&[0b00000001], 7).unwrap();
+        assert_eq!(reader.unsigned_iter_flagged().next(), Some((0, true)));
+    }
+
+    #[test]
+    fn next_unsigned_plus_one_at_u64_max() {
+        // 64 leading zeros followed by `1` and 64 zeros
+        let mut data = [0u8; 17];
+        data[8] = 0b10000000;
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        assert_eq!(reader.next_unsigned_plus_one(), None);
+
+        // 63 leading zeros followed by `1` and 63 ones - u64::MAX - 1
+        let mut data = [0u8; 16];
+        data[7] = 0b00000001;
+        data[8..].fill(0b11111111);
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+        assert_eq!(reader.next_unsigned_plus_one(), NonZeroU64::new(u64::MAX));
+    }
+
+    #[test]
+    fn next_unsigned_within_exact_budget() {
+        // 1 - 0, 00110 - 5, 00000000111111111 - 510
+        let data = [0b10011000, 0b00000011, 0b11111110];
+        let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+
+        let mut remaining_bits = 0;
+        assert_eq!(reader.next_unsigned_within(&mut remaining_bits), None);
+        assert_eq!(reader.position(), BitPosition::new(0, 0));
+
+        let mut remaining_bits = 1 + 5 + 17;
+        assert_eq!(reader.next_unsigned_within(&mut remaining_bits), Some(0));
+        assert_eq!(reader.next_unsigned_within(&mut remaining_bits), Some(5));
+        assert_eq!(reader.next_unsigned_within(&mut remaining_bits), Some(510));
+        assert_eq!(remaining_bits, 0);
+
+        // Running out of bits in the prefix behaves like `next_unsigned`
+        let mut remaining_bits = 100;
+        assert_eq!(reader.next_unsigned_within(&mut remaining_bits), None);
+        assert_eq!(remaining_bits, 100);
+        assert_eq!(reader.position(), BitPosition::new(3, 0));
+    }
+
+    #[test]
+    fn small_values_table_matches_bit_loop() {
+        for byte in 0..=255u8 {
+            // Trailing byte so that codewords longer than 8 bits can still complete
+            let data = [byte, 0b11111111];
+
+            let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+            let mut iter = BitIterator::new(&data, 0);
+            assert_eq!(reader.next_unsigned(), read_unsigned(&mut iter));
+            assert_eq!(reader.iter.index, iter.index);
+            assert_eq!(reader.iter.bit_pos, iter.bit_pos);
+        }
+    }
 }
@@ -1,3 +1,6 @@
+use crate::error::EncodeError;
+use core::num::NonZeroU64;
+
 /// An Exponential-Golomb writer.
 pub struct ExpGolombEncoder<'a> {
     bit_buf: BitBuffer<'a>,
@@ -9,7 +12,7 @@ impl<'a> ExpGolombEncoder<'a> {
     /// `start` denotes the starting position in the first byte of `buf` and goes from 0 (first) to
     ///  7 (last). This function returns `None` if the buffer is empty or if `start` is  not within
     /// \[0, 7\].
-    /// 
+    ///
     /// # Examples
     ///
     /// ```
@@ -32,7 +35,85 @@ impl<'a> ExpGolombEncoder<'a> {
         })
     }
 
-    /// Encode a `u64` into the buffer. Returns `None` if the buffer is full.
+    /// Create an `ExpGolombEncoder` that resumes writing at a position saved with
+    /// [`ExpGolombEncoder::state`], possibly on a different buffer. Returns `None` if the position
+    /// is past the end of `buf`.
+    ///
+    /// Unlike [`ExpGolombEncoder::new`], an empty buffer is accepted when paired with the default
+    /// state, producing an encoder where every write fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::{EncoderState, ExpGolombEncoder};
+    /// let mut buf = [0u8; 2];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// writer.put_unsigned(5).unwrap();
+    /// let state = writer.state();
+    /// writer.close();
+    ///
+    /// let mut writer = ExpGolombEncoder::from_state(&mut buf, state).unwrap();
+    /// writer.put_unsigned(4).unwrap();
+    /// writer.close();
+    /// assert_eq!(buf, [0b00110001, 0b01000000]);
+    ///
+    /// let mut writer = ExpGolombEncoder::from_state(&mut [], EncoderState::default()).unwrap();
+    /// assert!(writer.put_bit(true).is_none());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn from_state(buf: &'a mut [u8], state: EncoderState) -> Option<ExpGolombEncoder<'a>> {
+        if (state.index, state.bit_pos) > (buf.len(), 0) {
+            return None;
+        }
+        let mut bit_buf = BitBuffer::new(buf, state.bit_pos);
+        bit_buf.index = state.index;
+        bit_buf.start = (state.index, state.bit_pos);
+        Some(ExpGolombEncoder { bit_buf })
+    }
+
+    /// Rewind the encoder to bit `start` of the first byte so the buffer can be reused, e.g. for
+    /// encoding many small messages into the same scratch buffer. Returns `None` if the buffer is
+    /// empty or if `start` is not within \[0, 7\], in which case nothing is changed.
+    ///
+    /// Since values are written by setting bits in a zeroed buffer, the bits written since the
+    /// encoder was created or last reset are zeroed. The bits before the position it started at,
+    /// e.g. a header in the first byte, belong to the caller and are left as they are, so they have
+    /// to be zero if the new `start` is before the old one. Bytes after the current position are
+    /// not touched either.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombEncoder;
+    /// let mut buf = [0u8; 3];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// writer.put_unsigned(510).unwrap();
+    ///
+    /// writer.reset(2).unwrap();
+    /// writer.put_unsigned(1).unwrap();
+    /// assert_eq!(writer.close(), (0, 5));
+    /// assert_eq!(buf, [0b00010000, 0b00000000, 0b00000000]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn reset(&mut self, start: u32) -> Option<()> {
+        if self.bit_buf.buf.is_empty() || start > 7 {
+            return None;
+        }
+        self.bit_buf.reclaim();
+        let written_from = self.bit_buf.start;
+        if written_from < (self.bit_buf.index, self.bit_buf.bit_pos) {
+            self.bit_buf.clear_back_to(written_from);
+        }
+        self.bit_buf.index = 0;
+        self.bit_buf.bit_pos = start;
+        self.bit_buf.start = (0, start);
+        Some(())
+    }
+
+    /// Encode a `u64` into the buffer. Returns `None` if the buffer does not have room for the
+    /// whole codeword, in which case nothing is written.
     ///
     /// # Examples
     ///
@@ -44,15 +125,15 @@ impl<'a> ExpGolombEncoder<'a> {
     ///     writer.put_unsigned(i).unwrap();
     /// }
     /// writer.close();
-    /// 
+    ///
     /// assert_eq!(
     ///     buf,
     ///     [0b10100110, 0b01000010, 0b10011000, 0b11100010, 0b00000100, 0b10000000]
     /// );
     /// ```
-    /// 
+    ///
     /// This function guards against out of bounds indexing by returning `None`:
-    /// 
+    ///
     /// ```
     /// # use exp_golomb::ExpGolombEncoder;
     /// let mut buf = [0u8; 1];
@@ -61,24 +142,316 @@ impl<'a> ExpGolombEncoder<'a> {
     /// assert!(writer.put_unsigned(1).is_some());
     /// assert!(writer.put_unsigned(1).is_none());
     /// ```
+    ///
+    /// The largest values have the longest codewords. `u64::MAX - 1` takes 127 bits while
+    /// `u64::MAX` takes 129 bits since `u64::MAX + 1` needs 65 bits:
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombEncoder;
+    /// let mut buf = [0u8; 16];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// writer.put_unsigned(u64::MAX - 1).unwrap();
+    /// assert_eq!(writer.close(), (15, 7));
+    /// assert_eq!(buf[..7], [0; 7]);
+    /// assert_eq!(buf[7], 0b00000001);
+    /// assert_eq!(buf[8..15], [0b11111111; 7]);
+    /// assert_eq!(buf[15], 0b11111110);
+    ///
+    /// // Does not fit in 128 bits
+    /// let mut buf = [0u8; 16];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// assert!(writer.put_unsigned(u64::MAX).is_none());
+    /// assert_eq!(writer.close(), (0, 0));
+    ///
+    /// let mut buf = [0u8; 17];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// writer.put_unsigned(u64::MAX).unwrap();
+    /// assert_eq!(writer.close(), (16, 1));
+    /// assert_eq!(buf[8], 0b10000000);
+    /// ```
     #[inline]
     #[must_use]
     pub fn put_unsigned(&mut self, value: u64) -> Option<()> {
-        let xp1 = value.wrapping_add(1);
+        self.try_put_unsigned(value).ok()
+    }
+
+    /// Encode a `u64` into the buffer, returning [`EncodeError::BufferFull`] instead of
+    /// `None` when it does not fit. See [`ExpGolombEncoder::put_unsigned`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::{EncodeError, ExpGolombEncoder};
+    /// let mut buf = [0u8; 1];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// assert_eq!(writer.try_put_unsigned(2), Ok(()));
+    /// assert_eq!(writer.try_put_unsigned(7), Err(EncodeError::BufferFull));
+    /// ```
+    #[inline]
+    pub fn try_put_unsigned(&mut self, value: u64) -> Result<(), EncodeError> {
+        if self.bit_buf.remaining_bits() < unsigned_encoded_len(value) as usize {
+            return Err(EncodeError::BufferFull);
+        }
+
+        let res = match value.checked_add(1) {
+            Some(xp1) => {
+                let bytes = xp1.to_be_bytes();
+                let lz = xp1.leading_zeros();
+                let start = (lz / 8) as usize;
+                let bit_start = lz - (lz / 8 * 8);
+
+                let num_zeros = 64 - lz - 1;
+                self.bit_buf
+                    .put_zeros(num_zeros)
+                    .and_then(|_| self.bit_buf.put_bytes(&bytes[start..], bit_start))
+            }
+            // `u64::MAX + 1` is a one followed by 64 zeros
+            None => self
+                .bit_buf
+                .put_zeros(u64::BITS)
+                .and_then(|_| self.bit_buf.put_bit(true))
+                .and_then(|_| self.bit_buf.put_zeros(u64::BITS)),
+        };
+        res.ok_or(EncodeError::BufferFull)
+    }
+
+    /// Encode any unsigned integer that converts losslessly into a `u64`, so that narrower types can
+    /// be passed without casting. This is the same as [`ExpGolombEncoder::put_unsigned`] otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombEncoder;
+    /// let mut buf = [0u8; 1];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// writer.put_unsigned_from(1u8).unwrap();
+    /// writer.put_unsigned_from(2u16).unwrap();
+    /// writer.put_unsigned_from(0u32).unwrap();
+    /// writer.close();
+    /// assert_eq!(buf[0], 0b01001110);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn put_unsigned_from<T: Into<u64>>(&mut self, value: T) -> Option<()> {
+        self.put_unsigned(value.into())
+    }
+
+    /// Encode all of `values` as unsigned integers, returning the total number of bits written,
+    /// which is the same as [`unsigned_sequence_len_bits`]. Returns `None` if the buffer runs out,
+    /// in which case the values before the one that did not fit have been written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombEncoder;
+    /// let mut buf = [0u8; 4];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// assert_eq!(writer.put_unsigned_all(&[1, 5, 510, 4]), Some(30));
+    /// assert_eq!(buf, [0b01000110, 0b00000000, 0b11111111, 0b10010100]);
+    ///
+    /// let mut buf = [0u8; 2];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// assert_eq!(writer.put_unsigned_all(&[1, 5, 510]), None);
+    /// assert_eq!(writer.close(), (1, 0));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn put_unsigned_all(&mut self, values: &[u64]) -> Option<usize> {
+        let mut total_bits = 0;
+        for &value in values {
+            self.put_unsigned(value)?;
+            total_bits += unsigned_encoded_len(value) as usize;
+        }
+        Some(total_bits)
+    }
+
+    /// Encode an unsigned integer and pad it with zeros to a field of `total_bits` bits. Returns
+    /// `None` if the codeword is longer than `total_bits` or if the field does not fit in the
+    /// buffer, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombEncoder;
+    /// let mut buf = [0u8; 2];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// writer.put_unsigned_padded(5, 8).unwrap();
+    /// // 00110 is 5 bits long
+    /// assert!(writer.put_unsigned_padded(5, 4).is_none());
+    /// writer.put_unsigned_padded(0, 1).unwrap();
+    /// assert_eq!(writer.close(), (1, 1));
+    /// assert_eq!(buf, [0b00110000, 0b10000000]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn put_unsigned_padded(&mut self, value: u64, total_bits: u32) -> Option<()> {
+        let len = unsigned_encoded_len(value);
+        if len > total_bits || self.bit_buf.remaining_bits() < total_bits as usize {
+            return None;
+        }
+        self.put_unsigned(value)?;
+        self.bit_buf.put_zeros(total_bits - len)
+    }
+
+    /// Encode `value - 1` as an unsigned integer, e.g. for the `_minus1` syntax elements of H.264
+    /// and H.265. This is the inverse of
+    /// [`ExpGolombDecoder::next_unsigned_plus_one`](crate::ExpGolombDecoder::next_unsigned_plus_one).
+    /// Returns `None` if the buffer is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombEncoder;
+    /// use std::num::NonZeroU64;
+    ///
+    /// let mut buf = [0u8; 1];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// writer.put_unsigned_minus_one(NonZeroU64::new(1).unwrap()).unwrap();
+    /// writer.put_unsigned_minus_one(NonZeroU64::new(6).unwrap()).unwrap();
+    /// assert_eq!(writer.close(), (0, 6));
+    /// // 1 - 0, 00110 - 5
+    /// assert_eq!(buf, [0b10011000]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn put_unsigned_minus_one(&mut self, value: NonZeroU64) -> Option<()> {
+        self.put_unsigned(value.get() - 1)
+    }
+
+    /// Encode an unsigned integer with an escape for large values, which bounds the length of every
+    /// codeword. Values below `threshold` are written as usual. The rest are written as one more
+    /// leading zero than any of the smaller values can have, followed by `value - threshold` in
+    /// `escape_bits` bits. Values are read back with
+    /// [`ExpGolombDecoder::next_unsigned_escaped`](crate::ExpGolombDecoder::next_unsigned_escaped).
+    ///
+    /// Returns `None` if `escape_bits` is more than 64, if `value - threshold` does not fit in
+    /// `escape_bits` bits or if the buffer is full, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombEncoder;
+    /// let mut buf = [0u8; 2];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// // Values below 3 take at most one leading zero so the escape is two zeros
+    /// writer.put_unsigned_escaped(2, 3, 8).unwrap();
+    /// writer.put_unsigned_escaped(1000, 3, 10).unwrap();
+    /// assert!(writer.put_unsigned_escaped(1000, 3, 8).is_none());
+    /// assert_eq!(writer.close(), (1, 7));
+    /// // 011 - 2, 00 - escape, 1111100101 - 997
+    /// assert_eq!(buf, [0b01100111, 0b11001010]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn put_unsigned_escaped(
+        &mut self,
+        value: u64,
+        threshold: u64,
+        escape_bits: u32,
+    ) -> Option<()> {
+        if value < threshold {
+            return self.put_unsigned(value);
+        }
+        let raw = value - threshold;
+        if escape_bits > u64::BITS || raw.checked_shr(escape_bits).unwrap_or(0) != 0 {
+            return None;
+        }
+        let num_zeros = threshold.checked_ilog2().unwrap_or(0) + 1;
+        if self.bit_buf.remaining_bits() < num_zeros as usize + escape_bits as usize {
+            return None;
+        }
+        self.bit_buf.put_zeros(num_zeros)?;
+        self.put_n_bits(raw, escape_bits)
+    }
+
+    /// Encode an `i64` into the buffer. Returns `None` if the buffer is full.
+    ///
+    /// Values are mapped positive first as in the `se(v)` of H.264, i.e. 1 is written as code number
+    /// 1, -1 as 2 and so on. See [`ExpGolombEncoder::put_signed_neg_first`] for the opposite
+    /// convention.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombEncoder;
+    /// let mut buf = [0u8; 6];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// for i in [0, 1, -1, 2, -2, 3, -3, 4, -4] {
+    ///     writer.put_signed(i).unwrap();
+    /// }
+    /// writer.close();
+    ///
+    /// assert_eq!(
+    ///     buf,
+    ///     [0b10100110, 0b01000010, 0b10011000, 0b11100010, 0b00000100, 0b10000000]
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn put_signed(&mut self, value: i64) -> Option<()> {
+        self.try_put_signed(value).ok()
+    }
 
-        let bytes = xp1.to_be_bytes();
-        let lz = xp1.leading_zeros();
-        let start = (lz / 8) as usize;
-        let bit_start = lz - (lz / 8 * 8);
+    /// Encode an `i64` into the buffer, returning [`EncodeError::BufferFull`] instead of
+    /// `None` when it does not fit. See [`ExpGolombEncoder::put_signed`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::{EncodeError, ExpGolombEncoder};
+    /// let mut buf = [0u8; 1];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// assert_eq!(writer.try_put_signed(-1), Ok(()));
+    /// assert_eq!(writer.try_put_signed(4), Err(EncodeError::BufferFull));
+    /// ```
+    #[inline]
+    pub fn try_put_signed(&mut self, value: i64) -> Result<(), EncodeError> {
+        self.try_put_unsigned(signed_to_unsigned(value))
+    }
 
-        let num_zeros = 64 - lz - 1;
-        self.bit_buf.put_zeros(num_zeros);
+    /// Encode any signed integer that converts losslessly into an `i64`, so that narrower types can
+    /// be passed without casting. This is the same as [`ExpGolombEncoder::put_signed`] otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombEncoder;
+    /// let mut buf = [0u8; 1];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// writer.put_signed_from(1i8).unwrap();
+    /// writer.put_signed_from(-1i16).unwrap();
+    /// writer.close();
+    /// assert_eq!(buf[0], 0b01001100);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn put_signed_from<T: Into<i64>>(&mut self, value: T) -> Option<()> {
+        self.put_signed(value.into())
+    }
 
-        self.bit_buf.put_bytes(&bytes[start..], bit_start)
+    /// Encode a signed integer into the buffer with negative values first, i.e. -1 is written as
+    /// code number 1, 1 as 2 and so on. Returns `None` if the buffer is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombEncoder;
+    /// let mut buf = [0u8; 2];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// for i in [0, -1, 1, -2] {
+    ///     writer.put_signed_neg_first(i).unwrap();
+    /// }
+    /// writer.close();
+    /// assert_eq!(buf, [0b10100110, 0b01000000]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn put_signed_neg_first(&mut self, value: i64) -> Option<()> {
+        self.put_unsigned(signed_to_unsigned_neg_first(value))
     }
 
     /// Write a single bit to the buffer. Returns `None` if the buffer is full.
-    /// 
+    ///
     /// # Examples
     ///
     /// ```
@@ -98,8 +471,391 @@ impl<'a> ExpGolombEncoder<'a> {
         self.bit_buf.put_bit(value)
     }
 
+    /// Write a single bit to the buffer, returning [`EncodeError::BufferFull`] instead of `None`
+    /// when it is full. See [`ExpGolombEncoder::put_bit`].
+    #[inline]
+    pub fn try_put_bit(&mut self, value: bool) -> Result<(), EncodeError> {
+        self.bit_buf.put_bit(value).ok_or(EncodeError::BufferFull)
+    }
+
+    /// Write a run of `count` bits that are all set to `value`. Whole bytes inside the run are
+    /// written at once, so this is much faster than calling [`ExpGolombEncoder::put_bit`] in a
+    /// loop. Returns `None` if the buffer does not have room for the whole run, in which case
+    /// nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombEncoder;
+    /// let mut buf = [0u8; 3];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 3).unwrap();
+    /// writer.put_repeated_bit(true, 14).unwrap();
+    /// writer.put_repeated_bit(false, 2).unwrap();
+    /// writer.put_repeated_bit(true, 2).unwrap();
+    /// assert!(writer.put_repeated_bit(true, 4).is_none());
+    /// writer.close();
+    /// assert_eq!(buf, [0b00011111, 0b11111111, 0b10011000]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn put_repeated_bit(&mut self, value: bool, count: u32) -> Option<()> {
+        self.bit_buf.put_repeated(value, count)
+    }
+
+    /// Write the length of a run of zeros, which is simply `count` encoded as an unsigned integer.
+    /// [`encode_with_zero_runs`](crate::encode_with_zero_runs) uses this to collapse the zeros of
+    /// sparse data. Returns `None` if the buffer is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombEncoder;
+    /// let mut buf = [0u8; 1];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// writer.put_zero_run(5).unwrap();
+    /// assert_eq!(writer.close(), (0, 5));
+    /// assert_eq!(buf, [0b00110000]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn put_zero_run(&mut self, count: u64) -> Option<()> {
+        self.put_unsigned(count)
+    }
+
+    /// Write a codeword returned by
+    /// [`ExpGolombDecoder::next_unsigned_raw`](crate::ExpGolombDecoder::next_unsigned_raw) as is,
+    /// i.e. the lowest `len` bits of `bits`, most significant bit first. This allows copying
+    /// codewords between bitstreams without decoding and encoding them again. Returns `None` if
+    /// `len` is longer than the longest codeword (129 bits) or if the buffer does not have room for
+    /// all the bits, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::{ExpGolombDecoder, ExpGolombEncoder};
+    /// // 1 - 0, 00110 - 5
+    /// let data = [0b10011000];
+    /// let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    ///
+    /// let mut buf = [0u8; 1];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 2).unwrap();
+    /// while let Some((_, bits, len)) = reader.next_unsigned_raw() {
+    ///     writer.put_raw_codeword(bits, len).unwrap();
+    /// }
+    /// assert!(writer.put_raw_codeword(0, 130).is_none());
+    /// writer.close();
+    /// assert_eq!(buf, [0b00100110]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn put_raw_codeword(&mut self, bits: u128, len: u32) -> Option<()> {
+        const MAX_LEN: u32 = 2 * u64::BITS + 1;
+        if len > MAX_LEN || self.bit_buf.remaining_bits() < len as usize {
+            return None;
+        }
+        // Only the codeword of `u64::MAX` is longer than a `u128`, and its extra bit is a zero
+        let num_bits = len.min(u128::BITS);
+        self.bit_buf.put_zeros(len - num_bits)?;
+        self.bit_buf.put_low_bits(bits, num_bits)
+    }
+
+    /// Write the lowest `num_bits` bits of `value` to the buffer, most significant bit first.
+    /// Returns `None` if `num_bits` is more than 64 or if the buffer does not have room for all the
+    /// bits, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombEncoder;
+    /// let mut buf = [0u8; 2];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 4).unwrap();
+    /// writer.put_n_bits(0b101101, 6).unwrap();
+    /// assert!(writer.put_n_bits(0, 7).is_none());
+    /// assert!(writer.put_n_bits(0, 65).is_none());
+    /// writer.close();
+    /// assert_eq!(buf, [0b00001011, 0b01000000]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn put_n_bits(&mut self, value: u64, num_bits: u32) -> Option<()> {
+        self.try_put_n_bits(value, num_bits).ok()
+    }
+
+    /// Write the lowest `num_bits` bits of `value` to the buffer, returning an [`EncodeError`]
+    /// instead of `None` on failure. See [`ExpGolombEncoder::put_n_bits`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::{EncodeError, ExpGolombEncoder};
+    /// let mut buf = [0u8; 1];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// assert_eq!(writer.try_put_n_bits(0, 65), Err(EncodeError::InvalidWidth));
+    /// assert_eq!(writer.try_put_n_bits(0, 9), Err(EncodeError::BufferFull));
+    /// assert_eq!(writer.try_put_n_bits(0, 8), Ok(()));
+    /// ```
+    #[inline]
+    pub fn try_put_n_bits(&mut self, value: u64, num_bits: u32) -> Result<(), EncodeError> {
+        if num_bits > u64::BITS {
+            return Err(EncodeError::InvalidWidth);
+        }
+        if self.bit_buf.remaining_bits() < num_bits as usize {
+            return Err(EncodeError::BufferFull);
+        }
+        self.bit_buf
+            .put_low_bits(value as u128, num_bits)
+            .ok_or(EncodeError::BufferFull)
+    }
+
+    /// Pad with zeros up to the next byte boundary. Does nothing if the encoder is already at one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombEncoder;
+    /// let mut buf = [0u8; 2];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// writer.put_unsigned(1).unwrap();
+    /// writer.align_to_byte();
+    /// assert_eq!(writer.state().index(), 1);
+    /// writer.align_to_byte();
+    /// assert_eq!(writer.close(), (1, 0));
+    /// ```
+    #[inline]
+    pub fn align_to_byte(&mut self) {
+        if self.bit_buf.bit_pos != 0 {
+            self.bit_buf.bit_pos = 0;
+            self.bit_buf.index += 1;
+        }
+    }
+
+    /// Pad with zeros up to the next byte boundary and then copy `bytes` into the buffer, e.g. to
+    /// embed a byte-aligned payload. Returns `None` if the buffer does not have room for the
+    /// padding and all of `bytes`, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombEncoder;
+    /// let mut buf = [0u8; 4];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// writer.put_unsigned(1).unwrap();
+    /// writer.align_and_put_bytes(&[0xAB, 0xCD]).unwrap();
+    /// assert!(writer.align_and_put_bytes(&[0x01, 0x02]).is_none());
+    /// writer.put_unsigned(0).unwrap();
+    /// assert_eq!(writer.close(), (3, 1));
+    /// assert_eq!(buf, [0b01000000, 0xAB, 0xCD, 0b10000000]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn align_and_put_bytes(&mut self, bytes: &[u8]) -> Option<()> {
+        self.bit_buf.reclaim();
+        let start = self.bit_buf.index + (self.bit_buf.bit_pos != 0) as usize;
+        let end = start.checked_add(bytes.len())?;
+        self.bit_buf.buf.get_mut(start..end)?.copy_from_slice(bytes);
+        self.bit_buf.index = end;
+        self.bit_buf.bit_pos = 0;
+        Some(())
+    }
+
+    /// Write the `rbsp_trailing_bits` of H.264/H.265, i.e. a `1` followed by zeros up to the next
+    /// byte boundary. Returns `None` if the buffer is full, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombEncoder;
+    /// let mut buf = [0u8; 2];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// writer.put_unsigned(1).unwrap();
+    /// writer.put_rbsp_trailing_bits().unwrap();
+    /// assert_eq!(writer.close(), (1, 0));
+    /// assert_eq!(buf, [0b01010000, 0]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn put_rbsp_trailing_bits(&mut self) -> Option<()> {
+        self.bit_buf.put_bit(true)?;
+        self.align_to_byte();
+        Some(())
+    }
+
+    /// Consumes the `ExpGolombEncoder` after writing the RBSP trailing bits, returning the number of
+    /// bytes of `buf` that make up the payload. Returns `None` if the buffer is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombEncoder;
+    /// let mut buf = [0u8; 4];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// writer.put_unsigned(510).unwrap();
+    /// assert_eq!(writer.finish_rbsp(), Some(3));
+    /// assert_eq!(buf, [0b00000000, 0b11111111, 0b11000000, 0]);
+    ///
+    /// // No room for the trailing bits
+    /// let mut buf = [0u8; 1];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// writer.put_n_bits(0, 8).unwrap();
+    /// assert_eq!(writer.finish_rbsp(), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn finish_rbsp(mut self) -> Option<usize> {
+        self.put_rbsp_trailing_bits()?;
+        Some(self.bit_buf.index)
+    }
+
+    /// Returns the current write position so that encoding can be resumed later with
+    /// [`ExpGolombEncoder::from_state`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombEncoder;
+    /// let mut buf = [0u8; 1];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 2).unwrap();
+    /// writer.put_unsigned(1).unwrap();
+    /// let state = writer.state();
+    /// assert_eq!((state.index(), state.bit_pos()), (0, 5));
+    /// ```
+    #[inline]
+    pub fn state(&self) -> EncoderState {
+        EncoderState {
+            index: self.bit_buf.index - self.bit_buf.drained,
+            bit_pos: self.bit_buf.bit_pos,
+        }
+    }
+
+    /// Returns the current write position as a single number of bits from the most significant
+    /// bit of `buf[0]`, e.g. to compute the size of what was written since an earlier position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombEncoder;
+    /// let mut buf = [0u8; 2];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 2).unwrap();
+    /// assert_eq!(writer.position_bits(), 2);
+    /// writer.put_unsigned(5).unwrap();
+    /// writer.put_unsigned(1).unwrap();
+    /// assert_eq!(writer.position_bits(), 10);
+    /// ```
+    #[inline]
+    pub fn position_bits(&self) -> u64 {
+        (self.bit_buf.index - self.bit_buf.drained) as u64 * 8 + self.bit_buf.bit_pos as u64
+    }
+
+    /// Returns the bytes that have been completely written so far, e.g. to compute a checksum
+    /// before the encoder is closed. A partially written byte is not included since more bits may
+    /// still be added to it, and neither are bytes already returned by
+    /// [`ExpGolombEncoder::drain_complete_bytes`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombEncoder;
+    /// let mut buf = [0u8; 3];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// writer.put_unsigned(510).unwrap();
+    /// assert_eq!(writer.written_bytes(), [0b00000000, 0b11111111]);
+    /// writer.put_unsigned(0).unwrap();
+    /// assert_eq!(writer.written_bytes(), [0b00000000, 0b11111111]);
+    /// ```
+    #[inline]
+    pub fn written_bytes(&self) -> &[u8] {
+        &self.bit_buf.buf[self.bit_buf.drained..self.bit_buf.index]
+    }
+
+    /// Returns the capacity of the buffer in bits, i.e. `buf.len() * 8`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombEncoder;
+    /// let mut buf = [0u8; 2];
+    /// let writer = ExpGolombEncoder::new(&mut buf, 3).unwrap();
+    /// assert_eq!(writer.len_bits(), 16);
+    /// ```
+    #[inline]
+    pub fn len_bits(&self) -> usize {
+        self.bit_buf.buf.len() * 8
+    }
+
+    /// Undo everything written since `state` was saved with [`ExpGolombEncoder::state`], e.g. to
+    /// discard a trial encoding. The bits written since then are cleared so that the buffer can be
+    /// written to again. Returns `None` if `state` is after the current position, in which case
+    /// nothing is changed. A state saved before a call to
+    /// [`ExpGolombEncoder::drain_complete_bytes`] no longer applies after it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombEncoder;
+    /// let mut buf = [0u8; 3];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// writer.put_unsigned(1).unwrap();
+    /// let checkpoint = writer.state();
+    ///
+    /// writer.put_unsigned(510).unwrap();
+    /// writer.rollback(checkpoint).unwrap();
+    /// writer.put_unsigned(5).unwrap();
+    /// assert_eq!(writer.close(), (1, 0));
+    /// assert_eq!(buf, [0b01000110, 0, 0]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn rollback(&mut self, state: EncoderState) -> Option<()> {
+        // States are saved relative to the compacted buffer
+        self.bit_buf.reclaim();
+        let from = (state.index, state.bit_pos);
+        let to = (self.bit_buf.index, self.bit_buf.bit_pos);
+        if from > to {
+            return None;
+        }
+        if from < to {
+            self.bit_buf.clear_back_to(from);
+            self.bit_buf.start = self.bit_buf.start.min(from);
+        }
+        Some(())
+    }
+
+    /// Returns the bytes that have been completely written since the last call, e.g. to pass them
+    /// on to a stream while the encoder keeps going. A partially written byte is kept until it is
+    /// completed.
+    ///
+    /// The space taken by the returned bytes is reused by the next write, which moves the pending
+    /// data to the start of the buffer. Positions, states and the result of
+    /// [`ExpGolombEncoder::close`] are reported as if that had already happened, so they count from
+    /// the start of the pending data right after a call. Positions and states saved before a call
+    /// therefore no longer apply afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombEncoder;
+    /// let mut buf = [0u8; 3];
+    /// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    /// let mut out = Vec::new();
+    /// for value in [1, 5, 510, 4] {
+    ///     if writer.put_unsigned(value).is_none() {
+    ///         out.extend_from_slice(writer.drain_complete_bytes());
+    ///         writer.put_unsigned(value).unwrap();
+    ///     }
+    /// }
+    /// out.extend_from_slice(writer.drain_complete_bytes());
+    /// let (index, bit_pos) = writer.close();
+    /// assert_eq!((index, bit_pos), (0, 6));
+    /// out.push(buf[0]);
+    /// assert_eq!(out, [0b01000110, 0b00000000, 0b11111111, 0b10010100]);
+    /// ```
+    #[inline]
+    pub fn drain_complete_bytes(&mut self) -> &[u8] {
+        self.bit_buf.drain_complete_bytes()
+    }
+
     /// Consumes the `ExpGolombEncoder`, returning the bit position one past the last written bit.
-    /// 
+    ///
     /// # Examples
     ///
     /// ```
@@ -110,15 +866,306 @@ impl<'a> ExpGolombEncoder<'a> {
     /// assert_eq!(writer.close(), (0, 3));
     /// ```
     #[inline]
-    pub fn close(self) -> (usize, u32) {
+    pub fn close(mut self) -> (usize, u32) {
+        self.bit_buf.reclaim();
         (self.bit_buf.index, self.bit_buf.bit_pos)
     }
 }
 
+/// Encode a single unsigned value into a zeroed stack array without setting up a buffer and an
+/// encoder. The array is large enough for any codeword, the longest being the 129 bits of
+/// `u64::MAX`. Also returned is the bit position one past the codeword in the same form as
+/// [`ExpGolombEncoder::close`].
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::encode_one_unsigned;
+/// let (bytes, index, bit_pos) = encode_one_unsigned(510);
+/// assert_eq!((index, bit_pos), (2, 1));
+/// assert_eq!(bytes[..3], [0b00000000, 0b11111111, 0b10000000]);
+///
+/// let (_, index, bit_pos) = encode_one_unsigned(u64::MAX);
+/// assert_eq!((index, bit_pos), (16, 1));
+/// ```
+#[inline]
+pub fn encode_one_unsigned(value: u64) -> ([u8; 17], usize, u32) {
+    let mut buf = [0u8; 17];
+    let mut writer = ExpGolombEncoder {
+        bit_buf: BitBuffer::new(&mut buf, 0),
+    };
+    // Cannot fail since the array fits the longest codeword
+    let _ = writer.put_unsigned(value);
+    let (index, bit_pos) = writer.close();
+    (buf, index, bit_pos)
+}
+
+/// Encode a single unsigned value into `buf` starting at bit `start` of its first byte, returning
+/// the bit position one past the codeword in the same form as [`ExpGolombEncoder::close`]. Returns
+/// `None` under the same conditions as [`ExpGolombEncoder::new`] and
+/// [`ExpGolombEncoder::put_unsigned`].
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::put_one_unsigned;
+/// let mut buf = [0u8; 2];
+/// assert_eq!(put_one_unsigned(&mut buf, 6, 5), Some((1, 3)));
+/// assert_eq!(buf, [0b00000000, 0b11000000]);
+///
+/// assert_eq!(put_one_unsigned(&mut buf, 8, 5), None);
+/// assert_eq!(put_one_unsigned(&mut [0u8; 1], 6, 5), None);
+/// ```
+#[inline]
+#[must_use]
+pub fn put_one_unsigned(buf: &mut [u8], start: u32, value: u64) -> Option<(usize, u32)> {
+    let mut writer = ExpGolombEncoder::new(buf, start)?;
+    writer.put_unsigned(value)?;
+    Some(writer.close())
+}
+
+/// Returns `true` if the first `num_bits` bits of `a` and `b` are equal, ignoring the rest, e.g. to
+/// compare two encodings whose final bytes are only partially written. Bits are compared starting
+/// from the most significant bit of the first byte. Returns `false` if either slice is shorter than
+/// `num_bits`.
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::bits_equal;
+/// let a = [0b01000110, 0b10000000];
+/// let b = [0b01000110, 0b10111111];
+/// assert!(bits_equal(&a, &b, 10));
+/// assert!(!bits_equal(&a, &b, 11));
+/// assert!(!bits_equal(&a, &b, 17));
+/// ```
+#[inline]
+#[must_use]
+pub fn bits_equal(a: &[u8], b: &[u8], num_bits: usize) -> bool {
+    let num_bytes = num_bits.div_ceil(8);
+    let (Some(a), Some(b)) = (a.get(..num_bytes), b.get(..num_bytes)) else {
+        return false;
+    };
+    let full = num_bits / 8;
+    if a[..full] != b[..full] {
+        return false;
+    }
+    let rem = num_bits % 8;
+    rem == 0 || (a[full] ^ b[full]) >> (8 - rem) == 0
+}
+
+/// Truncate `vec` to the bytes written by an encoder, given the position returned by
+/// [`ExpGolombEncoder::close`]. A partially written final byte is kept.
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::{trim_to_written, ExpGolombEncoder};
+/// let mut buf = vec![0u8; 16];
+/// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+/// writer.put_unsigned(510).unwrap();
+/// let (index, bit_pos) = writer.close();
+///
+/// trim_to_written(&mut buf, index, bit_pos);
+/// assert_eq!(buf, [0b00000000, 0b11111111, 0b10000000]);
+/// ```
+#[cfg(feature = "std")]
+#[inline]
+pub fn trim_to_written(vec: &mut Vec<u8>, index: usize, bit_pos: u32) {
+    vec.truncate(index + (bit_pos > 0) as usize);
+}
+
+/// Encode `values` with their runs of zeros collapsed, e.g. for sparse coefficients. Each non-zero
+/// value is written as the number of zeros before it, using [`ExpGolombEncoder::put_zero_run`],
+/// followed by the value minus one. Trailing zeros are written as a final run. The result starts
+/// at the first bit and can be decoded with
+/// [`decode_with_zero_runs`](crate::decode_with_zero_runs).
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::{decode_with_zero_runs, encode_with_zero_runs};
+/// let values = [0, 0, 0, 0, 0, 7, 1, 0, 0];
+/// let buf = encode_with_zero_runs(&values);
+/// // 00110 - 5 zeros, 00111 - 7, 1 - no zeros, 1 - 1, 011 - 2 zeros
+/// assert_eq!(buf, [0b00110001, 0b11110110]);
+/// assert_eq!(decode_with_zero_runs(&buf, 0, 16).unwrap(), values);
+/// ```
+#[cfg(feature = "std")]
+pub fn encode_with_zero_runs(values: &[u64]) -> Vec<u8> {
+    let mut codes = Vec::new();
+    let mut run = 0;
+    for &value in values {
+        if value == 0 {
+            run += 1;
+        } else {
+            codes.extend([run, value - 1]);
+            run = 0;
+        }
+    }
+    if run > 0 {
+        codes.push(run);
+    }
+
+    let mut buf = vec![0u8; unsigned_sequence_len_bits(&codes).div_ceil(8)];
+    if let Some(mut writer) = ExpGolombEncoder::new(&mut buf, 0) {
+        for code in codes {
+            // Cannot fail since the buffer was sized for the codes
+            let _ = writer.put_unsigned(code);
+        }
+    }
+    buf
+}
+
+/// A saved write position of an [`ExpGolombEncoder`]. The default state is the start of a buffer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct EncoderState {
+    index: usize,
+    bit_pos: u32,
+}
+
+impl EncoderState {
+    /// Index of the byte that the next bit will be written to.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Position of the next bit within its byte, from 0 (first) to 7 (last).
+    #[inline]
+    pub fn bit_pos(&self) -> u32 {
+        self.bit_pos
+    }
+}
+
+/// Returns the number of bits in the codeword of an unsigned value.
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::unsigned_encoded_len;
+/// assert_eq!(unsigned_encoded_len(0), 1);
+/// assert_eq!(unsigned_encoded_len(510), 17);
+/// assert_eq!(unsigned_encoded_len(u64::MAX), 129);
+/// ```
+#[inline]
+pub fn unsigned_encoded_len(value: u64) -> u32 {
+    match value.checked_add(1) {
+        Some(xp1) => 2 * (u64::BITS - xp1.leading_zeros()) - 1,
+        None => 2 * u64::BITS + 1,
+    }
+}
+
+/// Returns the code number that [`ExpGolombEncoder::put_signed`] writes for `value`. Positive
+/// values map to the odd numbers and the rest to the even numbers, so `i64::MIN` maps to
+/// `u64::MAX`. This is the inverse of [`code_to_signed`](crate::code_to_signed).
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::signed_to_code;
+/// assert_eq!(signed_to_code(0), 0);
+/// assert_eq!(signed_to_code(1), 1);
+/// assert_eq!(signed_to_code(-1), 2);
+/// assert_eq!(signed_to_code(i64::MIN), u64::MAX);
+/// ```
+#[inline]
+pub fn signed_to_code(value: i64) -> u64 {
+    signed_to_unsigned(value)
+}
+
+/// Returns the number of bits in the codeword of a signed value.
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::signed_encoded_len;
+/// assert_eq!(signed_encoded_len(0), 1);
+/// assert_eq!(signed_encoded_len(-1), 3);
+/// assert_eq!(signed_encoded_len(i64::MIN), 129);
+/// ```
+#[inline]
+pub fn signed_encoded_len(value: i64) -> u32 {
+    unsigned_encoded_len(signed_to_unsigned(value))
+}
+
+/// Returns the total number of bits needed to encode a sequence of unsigned values. Round it up to
+/// the next multiple of 8 to get the size of the buffer to allocate.
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::{unsigned_sequence_len_bits, ExpGolombEncoder};
+/// let values = [1u64, 5, 510, 4];
+/// let num_bits = unsigned_sequence_len_bits(&values);
+/// assert_eq!(num_bits, 30);
+///
+/// let mut buf = vec![0u8; num_bits.div_ceil(8)];
+/// let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+/// for value in values {
+///     writer.put_unsigned(value).unwrap();
+/// }
+/// assert_eq!(writer.close(), (3, 6));
+/// ```
+#[inline]
+pub fn unsigned_sequence_len_bits(values: &[u64]) -> usize {
+    values
+        .iter()
+        .map(|&value| unsigned_encoded_len(value) as usize)
+        .sum()
+}
+
+/// Returns the total number of bits needed to encode a sequence of signed values. Round it up to
+/// the next multiple of 8 to get the size of the buffer to allocate.
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::signed_sequence_len_bits;
+/// assert_eq!(signed_sequence_len_bits(&[0, 1, -1, 2, -2]), 17);
+/// ```
+#[inline]
+pub fn signed_sequence_len_bits(values: &[i64]) -> usize {
+    values
+        .iter()
+        .map(|&value| signed_encoded_len(value) as usize)
+        .sum()
+}
+
+/// Maps a signed integer to its Exp-Golomb code number: positive values go to the odd numbers and
+/// the rest to the even numbers. This is the inverse of the mapping used by the decoder, which
+/// reads `u64::MAX` as `i64::MIN`.
+#[inline]
+pub(crate) fn signed_to_unsigned(value: i64) -> u64 {
+    if value > 0 {
+        2 * value as u64 - 1
+    } else if value == i64::MIN {
+        u64::MAX
+    } else {
+        2 * value.unsigned_abs()
+    }
+}
+
+/// Maps a signed integer to its Exp-Golomb code number with the negative values going to the odd
+/// numbers. This is the inverse of the mapping used by the decoder's `next_signed_neg_first`.
+#[inline]
+fn signed_to_unsigned_neg_first(value: i64) -> u64 {
+    if value < 0 {
+        2 * (!value as u64) + 1
+    } else {
+        2 * value as u64
+    }
+}
+
 struct BitBuffer<'a> {
     buf: &'a mut [u8],
     index: usize,
     bit_pos: u32,
+    /// Number of bytes at the start of `buf` that were returned by the last `drain_complete_bytes`
+    /// and have not been reclaimed yet
+    drained: usize,
+    /// Position of the first bit written by the encoder, the bits before it belong to the caller
+    start: (usize, u32),
 }
 
 impl<'a> BitBuffer<'a> {
@@ -128,11 +1175,62 @@ impl<'a> BitBuffer<'a> {
             buf,
             index: 0,
             bit_pos,
+            drained: 0,
+            start: (0, bit_pos),
+        }
+    }
+
+    #[inline]
+    fn drain_complete_bytes(&mut self) -> &[u8] {
+        self.reclaim();
+        self.drained = self.index;
+        &self.buf[..self.index]
+    }
+
+    /// Move the data after the bytes returned by `drain_complete_bytes` to the front of `buf` so
+    /// that their space can be written to
+    #[inline]
+    fn reclaim(&mut self) {
+        if self.drained > 0 {
+            let end = self.index + (self.bit_pos != 0) as usize;
+            self.buf.copy_within(self.drained..end, 0);
+            self.buf[end - self.drained..end].fill(0);
+            self.index -= self.drained;
+            // Everything before the drained bytes, including the bits of the caller, is gone
+            self.start = match self.start.0.checked_sub(self.drained) {
+                Some(index) => (index, self.start.1),
+                None => (0, 0),
+            };
+            self.drained = 0;
+        }
+    }
+
+    /// Clear the bits between `from` and the current position and move back to `from`
+    #[inline]
+    fn clear_back_to(&mut self, from: (usize, u32)) {
+        let to = (self.index, self.bit_pos);
+        debug_assert!(from <= to);
+        if from.0 == to.0 {
+            self.buf[from.0] &= !((0xFFu32 >> from.1) & !(0xFFu32 >> to.1)) as u8;
+        } else {
+            self.buf[from.0] &= !(0xFFu32 >> from.1) as u8;
+            self.buf[from.0 + 1..to.0].fill(0);
+            if to.1 > 0 {
+                self.buf[to.0] &= (0xFFu32 >> to.1) as u8;
+            }
         }
+        (self.index, self.bit_pos) = from;
+    }
+
+    /// Number of bits that can still be written, counting the space of drained bytes
+    #[inline]
+    fn remaining_bits(&self) -> usize {
+        (self.buf.len() - self.index + self.drained).saturating_mul(8) - self.bit_pos as usize
     }
 
     #[inline]
     fn put_bit(&mut self, value: bool) -> Option<()> {
+        self.reclaim();
         *self.buf.get_mut(self.index)? |= (value as u8) << (7 - self.bit_pos);
         self.bit_pos += 1;
         if self.bit_pos >= 8 {
@@ -144,18 +1242,65 @@ impl<'a> BitBuffer<'a> {
 
     #[inline]
     fn put_zeros(&mut self, num_zeros: u32) -> Option<()> {
-        // TODO: Suboptimal
-        for _ in 0..num_zeros {
-            self.put_bit(false)?;
+        self.put_repeated(false, num_zeros)
+    }
+
+    #[inline]
+    fn put_repeated(&mut self, value: bool, count: u32) -> Option<()> {
+        if self.remaining_bits() < count as usize {
+            return None;
+        }
+        self.reclaim();
+        if count == 0 {
+            return Some(());
+        }
+
+        // Bit offset of the end of the run relative to the start of the current byte
+        let end = self.bit_pos as usize + count as usize;
+        // The buffer is assumed to be zeroed so a run of zeros only needs to move the position
+        if value {
+            let last = self.index + end / 8;
+            let tail = (end % 8) as u32;
+            let head_end = if last == self.index { tail } else { 8 };
+            self.buf[self.index] |= ((0xFFu32 >> self.bit_pos) & !(0xFFu32 >> head_end)) as u8;
+            if last > self.index {
+                self.buf[self.index + 1..last].fill(0xFF);
+                if tail > 0 {
+                    self.buf[last] |= !(0xFFu32 >> tail) as u8;
+                }
+            }
         }
+        self.index += end / 8;
+        self.bit_pos = (end % 8) as u32;
         Some(())
     }
 
+    /// Write the lowest `num_bits` bits of `value`, most significant bit first
+    #[inline]
+    fn put_low_bits(&mut self, value: u128, num_bits: u32) -> Option<()> {
+        debug_assert!(num_bits <= u128::BITS);
+        let skipped = u128::BITS - num_bits;
+        // The bits above `num_bits` must be cleared or `put_bytes` would OR them into the bits
+        // that were already written
+        let value = value & u128::MAX.checked_shr(skipped).unwrap_or(0);
+        let bytes = value.to_be_bytes();
+        self.put_bytes(&bytes[(skipped / 8) as usize..], skipped % 8)
+    }
+
     #[inline]
     #[must_use]
     fn put_bytes(&mut self, bytes: &[u8], mut start_pos: u32) -> Option<()> {
+        self.reclaim();
+        if self.bit_pos == 0 && start_pos == 0 {
+            let end = self.index.checked_add(bytes.len())?;
+            self.buf.get_mut(self.index..end)?.copy_from_slice(bytes);
+            self.index = end;
+            return Some(());
+        }
+
         for &byte in bytes {
             while start_pos < 8 {
+                debug_assert!(self.bit_pos < 8 && start_pos < 8);
                 let data = ((byte as u32) << start_pos) >> self.bit_pos;
                 *self.buf.get_mut(self.index)? |= data as u8;
 
@@ -0,0 +1,85 @@
+use core::fmt;
+
+/// An error returned by the `try_*` methods of [`ExpGolombEncoder`](crate::ExpGolombEncoder).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EncodeError {
+    /// The buffer does not have room for the value. Nothing was written.
+    BufferFull,
+    /// The requested number of bits is more than a `u64` can hold.
+    InvalidWidth,
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodeError::BufferFull => f.write_str("buffer does not have room for the value"),
+            EncodeError::InvalidWidth => f.write_str("bit width is more than 64"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EncodeError {}
+
+/// The reason a codeword could not be decoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DecodeErrorKind {
+    /// The end of the bitstream was reached before a complete codeword was read.
+    EndOfStream,
+    /// The codeword is too long, either because its value exceeds the limits of a `u64` or
+    /// because it is longer than the limit set with
+    /// [`ExpGolombDecoder::max_codeword_bits`](crate::ExpGolombDecoder::max_codeword_bits).
+    Overflow,
+}
+
+/// An error returned by the `Result`-based decoding methods of
+/// [`ExpGolombDecoder`](crate::ExpGolombDecoder), annotated with where the codeword started.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DecodeError {
+    /// Why the codeword could not be decoded.
+    pub kind: DecodeErrorKind,
+    /// The offset of the first bit of the codeword, counted from the most significant bit of the
+    /// first byte of the buffer.
+    pub at_bit: usize,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            DecodeErrorKind::EndOfStream => {
+                write!(
+                    f,
+                    "bitstream ended in the middle of a codeword at bit {}",
+                    self.at_bit
+                )
+            }
+            DecodeErrorKind::Overflow => write!(f, "codeword at bit {} is too long", self.at_bit),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+/// An error returned by [`ExpGolombDecoder::try_new`](crate::ExpGolombDecoder::try_new).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NewError {
+    /// The buffer is empty.
+    EmptyBuffer,
+    /// The start bit is not within \[0, 7\].
+    InvalidStart(u32),
+}
+
+impl fmt::Display for NewError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NewError::EmptyBuffer => f.write_str("buffer is empty"),
+            NewError::InvalidStart(start) => {
+                write!(f, "start bit {start} is not within the first byte")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NewError {}
@@ -1,7 +1,58 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
 //! Utilities for Exponential-Golomb coding.
 
+mod bit_source;
+#[cfg(feature = "bytes")]
+mod buf;
+mod byte_order;
+mod chunked;
 mod decoder;
 mod encoder;
+mod error;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod partial;
+mod ring;
+#[cfg(feature = "std")]
+mod sequence;
+#[cfg(test)]
+mod test_util;
+#[cfg(feature = "std")]
+mod text;
 
-pub use self::{decoder::ExpGolombDecoder, encoder::ExpGolombEncoder};
+pub use self::{
+    bit_source::{BitSource, ExpGolombSourceDecoder, ZeroPaddedSource},
+    byte_order::{swap_word_bytes, ByteOrder, ByteOrderBitSource},
+    chunked::ExpGolombChunkedDecoder,
+    decoder::{
+        analyze_unsigned, code_to_signed, decode_in_range, decode_stats_signed,
+        decode_stats_unsigned, validate_unsigned, BitPosition, BitReaderRef, ExpGolombDecoder,
+        FieldSpec, StreamStats, UnsignedIter,
+    },
+    encoder::{
+        bits_equal, encode_one_unsigned, put_one_unsigned, signed_encoded_len,
+        signed_sequence_len_bits, signed_to_code, unsigned_encoded_len, unsigned_sequence_len_bits,
+        EncoderState, ExpGolombEncoder,
+    },
+    error::{DecodeError, DecodeErrorKind, EncodeError, NewError},
+    partial::{PartialDecoder, Progress},
+    ring::RingBitSource,
+};
+
+#[cfg(feature = "bytes")]
+pub use self::buf::BufBitSource;
+
+#[cfg(feature = "rayon")]
+pub use self::parallel::decode_segments;
+
+#[cfg(feature = "std")]
+pub use self::{
+    decoder::{
+        decode_all_unsigned, decode_all_unsigned_limited, decode_trace_unsigned,
+        decode_until_start_code, decode_with_zero_runs,
+    },
+    encoder::{encode_with_zero_runs, trim_to_written},
+    sequence::{DisplaySigned, ExpGolombSequence},
+    text::{format_codewords, parse_codewords},
+};
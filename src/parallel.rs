@@ -0,0 +1,81 @@
+use crate::decoder::ExpGolombDecoder;
+use rayon::prelude::*;
+
+/// Decode the unsigned values of independent segments of `buf` in parallel, e.g. slices or tiles
+/// whose bit boundaries are known. Each segment is given as
+/// `(start_index, start_bit, end_index, end_bit)` where the segment starts at bit `start_bit` of
+/// `buf[start_index]` and ends right before bit `end_bit` of `buf[end_index]`.
+///
+/// The values of each segment are decoded as by
+/// [`decode_all_unsigned`](crate::decode_all_unsigned) with the end of the segment treated as the
+/// end of the bitstream. An invalid segment, i.e. one that is empty, out of bounds or that ends
+/// before it starts, decodes to no values.
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::decode_segments;
+/// // 010 00110 | 00000000111111111 | 00101 - 1, 5 | 510 | 4
+/// let data = [0b01000110, 0b00000000, 0b11111111, 0b10010101];
+/// let segments = [(0, 0, 1, 0), (1, 0, 3, 1), (3, 1, 3, 6)];
+/// assert_eq!(
+///     decode_segments(&data, &segments),
+///     vec![vec![1, 5], vec![510], vec![4]]
+/// );
+/// ```
+pub fn decode_segments(buf: &[u8], boundaries: &[(usize, u32, usize, u32)]) -> Vec<Vec<u64>> {
+    boundaries
+        .par_iter()
+        .map(|&(start_index, start_bit, end_index, end_bit)| {
+            let mut values = Vec::new();
+            if let Some(mut reader) =
+                segment_decoder(buf, start_index, start_bit, end_index, end_bit)
+            {
+                while let Some(value) = reader.next_unsigned() {
+                    values.push(value);
+                }
+            }
+            values
+        })
+        .collect()
+}
+
+fn segment_decoder(
+    buf: &[u8],
+    start_index: usize,
+    start_bit: u32,
+    end_index: usize,
+    end_bit: u32,
+) -> Option<ExpGolombDecoder<'_>> {
+    if end_bit > 7 {
+        return None;
+    }
+    let len = end_index.checked_sub(start_index)?;
+    let end_bit = len.checked_mul(8)?.checked_add(end_bit as usize)?;
+    ExpGolombDecoder::new_bounded(buf.get(start_index..)?, start_bit, end_bit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode_all_unsigned;
+
+    #[test]
+    fn matches_sequential_decode() {
+        let data = [0b01000110, 0b00000000, 0b11111111, 0b10010101];
+        let whole = [(0, 0, data.len(), 0)];
+        assert_eq!(
+            decode_segments(&data, &whole),
+            [decode_all_unsigned(&data, 0)]
+        );
+
+        let invalid = [
+            (0, 8, 1, 0),
+            (1, 0, 0, 0),
+            (0, 0, 4, 1),
+            (5, 0, 5, 0),
+            (4, 0, 4, 0),
+        ];
+        assert!(decode_segments(&data, &invalid).iter().all(Vec::is_empty));
+    }
+}
@@ -0,0 +1,202 @@
+/// The result of feeding bytes to a [`PartialDecoder`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Progress {
+    /// All the bytes were consumed without completing a codeword. Feed more bytes to continue.
+    Pending,
+    /// A value was decoded. `consumed` is the number of bytes that were fully read; the byte at
+    /// `consumed`, if any, may still hold unread bits and must be the first byte of the next feed.
+    Done {
+        /// The decoded value.
+        value: u64,
+        /// Number of bytes fully read.
+        consumed: usize,
+    },
+    /// The coded value exceeds the limits of a `u64`. Decoding starts over with a new codeword at
+    /// the bit after the one that caused the overflow, with `consumed` as in [`Progress::Done`].
+    Overflow {
+        /// Number of bytes fully read.
+        consumed: usize,
+    },
+}
+
+/// An incremental Exponential-Golomb parser for bytes that arrive piece by piece.
+///
+/// Unlike [`ExpGolombDecoder`](crate::ExpGolombDecoder), this keeps the state of a partially read
+/// codeword between calls to [`PartialDecoder::feed`], so a codeword may be split across any
+/// number of feeds.
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::{PartialDecoder, Progress};
+/// // 00000000111111111 - 510, arriving one byte at a time
+/// let mut decoder = PartialDecoder::new(0).unwrap();
+/// assert_eq!(decoder.feed(&[0b00000000]), Progress::Pending);
+/// assert_eq!(decoder.feed(&[0b11111111]), Progress::Pending);
+///
+/// // 1 - 0, in the rest of the last byte
+/// let last = [0b11000000];
+/// assert_eq!(decoder.feed(&last), Progress::Done { value: 510, consumed: 0 });
+/// assert_eq!(decoder.feed(&last), Progress::Done { value: 0, consumed: 0 });
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PartialDecoder {
+    bit_pos: u32,
+    leading_zeros: u32,
+    in_suffix: bool,
+    remaining: u32,
+    suffix: u64,
+}
+
+impl PartialDecoder {
+    /// Create a new `PartialDecoder`.
+    ///
+    /// `start` denotes the starting position in the first byte that will be fed and goes from 0
+    /// (first) to 7 (last). This function returns `None` if `start` is not within \[0, 7\].
+    #[inline]
+    #[must_use]
+    pub fn new(start: u32) -> Option<PartialDecoder> {
+        if start > 7 {
+            return None;
+        }
+        Some(PartialDecoder {
+            bit_pos: start,
+            ..Default::default()
+        })
+    }
+
+    /// Continue decoding from `bytes` until a codeword is completed or the bytes run out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::{PartialDecoder, Progress};
+    /// // 010 00110 | 00101 011
+    /// let data = [0b01000110, 0b00101011];
+    /// let mut decoder = PartialDecoder::new(0).unwrap();
+    ///
+    /// let mut rest = &data[..];
+    /// let mut values = Vec::new();
+    /// while let Progress::Done { value, consumed } = decoder.feed(rest) {
+    ///     values.push(value);
+    ///     rest = &rest[consumed..];
+    /// }
+    /// assert_eq!(values, [1, 5, 4, 2]);
+    /// ```
+    #[inline]
+    pub fn feed(&mut self, bytes: &[u8]) -> Progress {
+        for (i, &byte) in bytes.iter().enumerate() {
+            while self.bit_pos < 8 {
+                let bit = (byte >> (7 - self.bit_pos)) & 1;
+                self.bit_pos += 1;
+
+                if let Some(result) = self.push_bit(bit) {
+                    let consumed = if self.bit_pos == 8 {
+                        self.bit_pos = 0;
+                        i + 1
+                    } else {
+                        i
+                    };
+                    return match result {
+                        Some(value) => Progress::Done { value, consumed },
+                        None => Progress::Overflow { consumed },
+                    };
+                }
+            }
+            self.bit_pos = 0;
+        }
+        Progress::Pending
+    }
+
+    /// Returns `Some` once the codeword is complete, holding `None` if the value overflowed.
+    #[inline]
+    fn push_bit(&mut self, bit: u8) -> Option<Option<u64>> {
+        if !self.in_suffix {
+            if bit == 0 {
+                self.leading_zeros += 1;
+                if self.leading_zeros > u64::BITS {
+                    return Some(self.finish());
+                }
+                return None;
+            }
+            self.in_suffix = true;
+            self.remaining = self.leading_zeros;
+        } else {
+            self.suffix = (self.suffix << 1) | bit as u64;
+            self.remaining -= 1;
+        }
+
+        if self.remaining == 0 {
+            Some(self.finish())
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn finish(&mut self) -> Option<u64> {
+        let value = if self.leading_zeros > u64::BITS {
+            None
+        } else {
+            let x = 1u64
+                .checked_shl(self.leading_zeros)
+                .map_or(u64::MAX, |v| v - 1);
+            x.checked_add(self.suffix)
+        };
+        *self = PartialDecoder {
+            bit_pos: self.bit_pos,
+            ..Default::default()
+        };
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+
+    #[test]
+    fn start_bit_validity() {
+        for i in 0..=7 {
+            assert!(PartialDecoder::new(i).is_some());
+        }
+        assert!(PartialDecoder::new(8).is_none());
+    }
+
+    #[test]
+    fn matches_decoder_for_every_split() {
+        test_util::for_each_split(|first, second, start| {
+            let mut decoder = PartialDecoder::new(start).unwrap();
+            let (mut rest, mut next) = (first, second);
+            test_util::assert_matches_contiguous(start, || loop {
+                match decoder.feed(rest) {
+                    Progress::Done { value, consumed } => {
+                        rest = &rest[consumed..];
+                        return Some(value);
+                    }
+                    Progress::Pending if !next.is_empty() => {
+                        (rest, next) = (next, &[]);
+                    }
+                    Progress::Pending => return None,
+                    Progress::Overflow { .. } => unreachable!(),
+                }
+            });
+        });
+    }
+
+    #[test]
+    fn overflow() {
+        // 65 leading zeros followed by `010`
+        let data = [0, 0, 0, 0, 0, 0, 0, 0, 0b00101000];
+        let mut decoder = PartialDecoder::new(0).unwrap();
+        assert_eq!(decoder.feed(&data), Progress::Overflow { consumed: 8 });
+        assert_eq!(
+            decoder.feed(&data[8..]),
+            Progress::Done {
+                value: 1,
+                consumed: 0
+            }
+        );
+    }
+}
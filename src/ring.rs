@@ -0,0 +1,125 @@
+use crate::bit_source::BitSource;
+
+/// A [`BitSource`] that reads from a ring buffer, where the data starts at `head` and wraps around
+/// to the start of the slice. Codewords can span the wrap point, so the data does not have to be
+/// copied out of the ring before decoding.
+///
+/// # Examples
+///
+/// ```
+/// use exp_golomb::{ExpGolombSourceDecoder, RingBitSource};
+///
+/// // 00000000111111111 - 510, starting at index 3 and wrapping around
+/// let ring = [0b11111111, 0b10000000, 0xAB, 0b00000000];
+/// let source = RingBitSource::new(&ring, 3, 3, 0).unwrap();
+/// let mut reader = ExpGolombSourceDecoder::new(source);
+/// assert_eq!(reader.next_unsigned(), Some(510));
+/// assert_eq!(reader.into_inner().consumed_bytes(), 2);
+/// ```
+pub struct RingBitSource<'a> {
+    buf: &'a [u8],
+    head: usize,
+    len: usize,
+    index: usize,
+    bit_pos: u32,
+}
+
+impl<'a> RingBitSource<'a> {
+    /// Create a new `RingBitSource` over the `len` bytes of `buf` starting at index `head`.
+    ///
+    /// `start` denotes the starting position in the byte at `head` and goes from 0 (first) to 7
+    /// (last). This function returns `None` if `len` is zero or more than `buf.len()`, if `head`
+    /// is not an index of `buf` or if `start` is not within \[0, 7\].
+    #[inline]
+    #[must_use]
+    pub fn new(buf: &'a [u8], head: usize, len: usize, start: u32) -> Option<RingBitSource<'a>> {
+        if len == 0 || len > buf.len() || head >= buf.len() || start > 7 {
+            return None;
+        }
+        Some(RingBitSource {
+            buf,
+            head,
+            len,
+            index: 0,
+            bit_pos: start,
+        })
+    }
+
+    /// Returns the number of bytes that have been completely read, i.e. how far the head of the
+    /// ring can be moved.
+    #[inline]
+    pub fn consumed_bytes(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the position of the next bit within the byte at [`RingBitSource::consumed_bytes`].
+    #[inline]
+    pub fn bit_pos(&self) -> u32 {
+        self.bit_pos
+    }
+}
+
+impl BitSource for RingBitSource<'_> {
+    #[inline]
+    fn next_bit(&mut self) -> Option<u8> {
+        if self.index == self.len {
+            return None;
+        }
+        // `head` and `index` are both less than `buf.len()` so this cannot overflow
+        let mut index = self.head + self.index;
+        if index >= self.buf.len() {
+            index -= self.buf.len();
+        }
+        let bit = (self.buf[index] >> (7 - self.bit_pos)) & 1;
+        self.bit_pos += 1;
+        if self.bit_pos == 8 {
+            self.bit_pos = 0;
+            self.index += 1;
+        }
+        Some(bit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_util, ExpGolombSourceDecoder};
+
+    #[test]
+    fn invalid_arguments() {
+        let ring = [0u8; 4];
+        assert!(RingBitSource::new(&ring, 0, 0, 0).is_none());
+        assert!(RingBitSource::new(&ring, 0, 5, 0).is_none());
+        assert!(RingBitSource::new(&ring, 4, 1, 0).is_none());
+        assert!(RingBitSource::new(&ring, 3, 4, 8).is_none());
+        assert!(RingBitSource::new(&ring, 3, 4, 7).is_some());
+    }
+
+    #[test]
+    fn codeword_spans_wrap_point() {
+        // 00000000111111111 - 510 from the last byte around to the first two, then 010 - 1
+        let ring = [0b11111111, 0b10100000, 0xAB, 0b00000000];
+        let source = RingBitSource::new(&ring, 3, 3, 0).unwrap();
+        let mut reader = ExpGolombSourceDecoder::new(source);
+        assert_eq!(reader.next_unsigned(), Some(510));
+        assert_eq!(reader.next_unsigned(), Some(1));
+        assert_eq!(reader.next_unsigned(), None);
+        let source = reader.into_inner();
+        assert_eq!((source.consumed_bytes(), source.bit_pos()), (3, 0));
+    }
+
+    #[test]
+    fn matches_contiguous_decoder_at_every_rotation() {
+        for head in 0..test_util::DATA.len() {
+            // Rotate the data so that it starts at `head`
+            let mut ring = test_util::DATA;
+            ring.rotate_right(head);
+
+            for start in 0..=7 {
+                let source = RingBitSource::new(&ring, head, ring.len(), start).unwrap();
+                let mut wrapped = ExpGolombSourceDecoder::new(source);
+                test_util::assert_matches_contiguous(start, || wrapped.next_unsigned());
+            }
+        }
+    }
+}
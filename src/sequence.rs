@@ -0,0 +1,121 @@
+use crate::decoder::{code_to_signed, decode_all_unsigned};
+use core::fmt;
+
+/// A sequence of unsigned Exp-Golomb values.
+///
+/// This is a thin wrapper over a `Vec<u64>` which can be used as a key in hash maps, e.g. to cache
+/// structures parsed from identical sequences of fields.
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::ExpGolombSequence;
+/// use std::collections::HashMap;
+///
+/// let data = [0b01000110, 0b00000000, 0b11111111, 0b10010101];
+/// let mut cache = HashMap::new();
+/// cache.insert(ExpGolombSequence::decode(&data, 0), "parsed");
+///
+/// let key = ExpGolombSequence::from(vec![1, 5, 510, 4]);
+/// assert_eq!(cache.get(&key), Some(&"parsed"));
+/// ```
+///
+/// The values are displayed separated by spaces:
+///
+/// ```
+/// # use exp_golomb::ExpGolombSequence;
+/// let seq = ExpGolombSequence::from(vec![1, 5, 510, 4]);
+/// assert_eq!(seq.to_string(), "1 5 510 4");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ExpGolombSequence(Vec<u64>);
+
+impl ExpGolombSequence {
+    /// Decode every unsigned value in `buf` into a sequence. See [`decode_all_unsigned`] for how
+    /// the end of the sequence is determined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombSequence;
+    /// let data = [0b01000110, 0b00000000, 0b11111111, 0b10010101];
+    /// let seq = ExpGolombSequence::decode(&data, 0);
+    /// assert_eq!(seq.as_slice(), [1, 5, 510, 4]);
+    /// ```
+    #[inline]
+    pub fn decode(buf: &[u8], start: u32) -> ExpGolombSequence {
+        ExpGolombSequence(decode_all_unsigned(buf, start))
+    }
+
+    /// Returns the values of the sequence.
+    #[inline]
+    pub fn as_slice(&self) -> &[u64] {
+        &self.0
+    }
+
+    /// Returns an object that displays the values mapped to signed integers as by
+    /// [`ExpGolombDecoder::next_signed`](crate::ExpGolombDecoder::next_signed).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exp_golomb::ExpGolombSequence;
+    /// let seq = ExpGolombSequence::from(vec![1, 5, 510, 4]);
+    /// assert_eq!(seq.display_signed().to_string(), "1 3 -255 -2");
+    /// ```
+    #[inline]
+    pub fn display_signed(&self) -> DisplaySigned<'_> {
+        DisplaySigned(&self.0)
+    }
+}
+
+impl fmt::Display for ExpGolombSequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_separated(f, self.0.iter())
+    }
+}
+
+/// Displays the values of an [`ExpGolombSequence`] as signed integers. Created by
+/// [`ExpGolombSequence::display_signed`].
+#[derive(Clone, Copy, Debug)]
+pub struct DisplaySigned<'a>(&'a [u64]);
+
+impl fmt::Display for DisplaySigned<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_separated(f, self.0.iter().map(|&k| code_to_signed(k)))
+    }
+}
+
+fn write_separated<T: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    values: impl Iterator<Item = T>,
+) -> fmt::Result {
+    for (i, value) in values.enumerate() {
+        if i > 0 {
+            f.write_str(" ")?;
+        }
+        write!(f, "{value}")?;
+    }
+    Ok(())
+}
+
+impl From<Vec<u64>> for ExpGolombSequence {
+    #[inline]
+    fn from(values: Vec<u64>) -> Self {
+        ExpGolombSequence(values)
+    }
+}
+
+impl From<ExpGolombSequence> for Vec<u64> {
+    #[inline]
+    fn from(seq: ExpGolombSequence) -> Self {
+        seq.0
+    }
+}
+
+impl FromIterator<u64> for ExpGolombSequence {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = u64>>(iter: T) -> Self {
+        ExpGolombSequence(iter.into_iter().collect())
+    }
+}
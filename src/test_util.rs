@@ -0,0 +1,35 @@
+//! Helpers shared by the unit tests of the different ways to feed a bitstream to a decoder.
+
+use crate::ExpGolombDecoder;
+
+/// Codewords of several lengths, the longest crossing two byte boundaries, followed by a partial
+/// one: `010` - 1, `00110` - 5, `00000000111111111` - 510, `00101` - 4, `01` and `0000000111`.
+pub(crate) const DATA: [u8; 6] = [
+    0b01000110, 0b00000000, 0b11111111, 0b10010101, 0b00000001, 0b11000000,
+];
+
+/// Calls `f` with [`DATA`] split in two at every byte and with every start bit.
+pub(crate) fn for_each_split(mut f: impl FnMut(&[u8], &[u8], u32)) {
+    for split in 0..=DATA.len() {
+        let (first, second) = DATA.split_at(split);
+        for start in 0..=7 {
+            f(first, second, start);
+        }
+    }
+}
+
+/// Checks that `next_unsigned` returns the same values as an [`ExpGolombDecoder`] over [`DATA`]
+/// that starts at `start`, up to and including the first `None`.
+pub(crate) fn assert_matches_contiguous(
+    start: u32,
+    mut next_unsigned: impl FnMut() -> Option<u64>,
+) {
+    let mut contiguous = ExpGolombDecoder::new(&DATA, start).unwrap();
+    loop {
+        let value = contiguous.next_unsigned();
+        assert_eq!(next_unsigned(), value, "start {start}");
+        if value.is_none() {
+            break;
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use crate::decoder::decode_trace_unsigned;
+
+/// Render the unsigned Exp-Golomb codewords in `buf` as a string of `0`s and `1`s with each
+/// codeword separated by a space. Any bits after the last complete codeword, e.g. the RBSP
+/// trailing bits, are appended as a final group. An empty string is returned if `buf` is empty or
+/// `start` is not within \[0, 7\].
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::format_codewords;
+/// // 010 00110 1 - 1, 5, 0 and 7 trailing zeros
+/// let data = [0b01000110, 0b10000000];
+/// assert_eq!(format_codewords(&data, 0), "010 00110 1 0000000");
+/// assert_eq!(format_codewords(&data, 3), "00110 1 0000000");
+/// ```
+pub fn format_codewords(buf: &[u8], start: u32) -> String {
+    let mut text = String::new();
+    if buf.is_empty() || start > 7 {
+        return text;
+    }
+
+    let mut push_bits = |from: usize, to: usize| {
+        if !text.is_empty() {
+            text.push(' ');
+        }
+        for i in from..to {
+            let bit = (buf[i / 8] >> (7 - i % 8)) & 1;
+            text.push(if bit == 0 { '0' } else { '1' });
+        }
+    };
+
+    let mut pos = start as usize;
+    for (start_bit, end_bit, _) in decode_trace_unsigned(buf, start) {
+        push_bits(start_bit, end_bit);
+        pos = end_bit;
+    }
+    if pos < buf.len() * 8 {
+        push_bits(pos, buf.len() * 8);
+    }
+    text
+}
+
+/// Pack a string of `0`s and `1`s into bytes, most significant bit first, ignoring any whitespace.
+/// The last byte is padded with zeros. This is the inverse of [`format_codewords`] with a `start`
+/// of 0.
+///
+/// # Panics
+///
+/// Panics if `s` contains characters other than `0`, `1` and whitespace.
+///
+/// # Examples
+///
+/// ```
+/// # use exp_golomb::parse_codewords;
+/// assert_eq!(parse_codewords("010 00110 1"), [0b01000110, 0b10000000]);
+/// assert_eq!(parse_codewords(""), []);
+/// ```
+pub fn parse_codewords(s: &str) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for (num_bits, c) in s.chars().filter(|c| !c.is_whitespace()).enumerate() {
+        let bit = match c {
+            '0' => 0,
+            '1' => 1,
+            _ => panic!("invalid character {c:?} in codeword string"),
+        };
+        if num_bits % 8 == 0 {
+            bytes.push(0);
+        }
+        *bytes.last_mut().unwrap() |= bit << (7 - num_bits % 8);
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let data = [0b01000110, 0b00000000, 0b11111111, 0b10010101, 0b00000000];
+        for len in 0..=data.len() {
+            assert_eq!(
+                parse_codewords(&format_codewords(&data[..len], 0)),
+                &data[..len]
+            );
+        }
+    }
+
+    #[test]
+    fn overflowing_codeword_is_kept_as_is() {
+        // 65 leading zeros
+        let data = [0u8; 9];
+        let text = format_codewords(&data, 0);
+        assert_eq!(text, "0".repeat(72));
+        assert_eq!(parse_codewords(&text), data);
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_character() {
+        let _ = parse_codewords("0102");
+    }
+}
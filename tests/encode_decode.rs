@@ -27,16 +27,561 @@ fn encode_decode_random() {
     let mut rng = rand::rngs::StdRng::seed_from_u64(SEED);
     let nums: Vec<_> = (0..NUM_VALS).map(|_| rng.gen::<u64>()).collect();
 
-    let mut buf = vec![0u8; 3 * 8 * NUM_VALS];
+    let len_bits = unsigned_sequence_len_bits(&nums);
+    let mut buf = vec![0u8; len_bits.div_ceil(8)];
     let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    assert_eq!(writer.put_unsigned_all(&nums), Some(len_bits));
+    writer.close();
+
+    let mut reader = ExpGolombDecoder::new(&buf, 0).unwrap();
+    for &num in &nums {
+        assert_eq!(reader.next_unsigned(), Some(num));
+    }
+}
+
+#[test]
+fn encode_decode_signed_all_start_offsets() {
+    const SEED: u64 = 0;
+    const NUM_VALS: usize = 1000;
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(SEED);
+    let mut nums = vec![0, 1, -1, 2, -2, 3, -3, i64::MAX, i64::MIN + 1, i64::MIN];
+    nums.extend((0..NUM_VALS).map(|_| rng.gen_range(-16..16)));
+    nums.extend((0..NUM_VALS).map(|_| rng.gen::<i64>() >> rng.gen_range(0..64)));
+
+    for start in 0..=7 {
+        let mut buf = vec![0u8; signed_sequence_len_bits(&nums).div_ceil(8) + 1];
+        let mut writer = ExpGolombEncoder::new(&mut buf, start).unwrap();
+
+        for &num in &nums {
+            writer.put_signed(num).unwrap();
+        }
+        writer.close();
+
+        let mut reader = ExpGolombDecoder::new(&buf, start).unwrap();
+        for &num in &nums {
+            assert_eq!(reader.next_signed(), Some(num));
+        }
+    }
+}
+
+#[test]
+fn skip_next_then_next_signed() {
+    // Values whose codewords cover every length
+    let mut nums = vec![0, i64::MIN, i64::MAX];
+    nums.extend((0..63).flat_map(|i| [1i64 << i, -(1i64 << i)]));
+
+    for window in nums.windows(3) {
+        let mut buf = vec![0u8; signed_sequence_len_bits(window).div_ceil(8) + 1];
+        let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+        for &num in window {
+            writer.put_signed(num).unwrap();
+        }
+        writer.put_bit(true).unwrap();
+        writer.close();
+
+        let mut reader = ExpGolombDecoder::new(&buf, 0).unwrap();
+        reader.skip_next();
+        reader.skip_next();
+        assert_eq!(reader.next_signed(), Some(window[2]));
+        assert_eq!(reader.next_bit(), Some(1));
+
+        let mut reader = ExpGolombDecoder::new(&buf, 0).unwrap();
+        assert_eq!(reader.next_signed(), Some(window[0]));
+        reader.skip_next();
+        reader.skip_next();
+        assert_eq!(reader.next_bit(), Some(1));
+    }
+}
+
+#[test]
+fn encode_decode_extremes() {
+    let nums = [u64::MAX, u64::MAX - 1, 0, u64::MAX, 1 << 63, (1 << 63) - 1];
+
+    for start in 0..=7 {
+        let mut buf = vec![0u8; unsigned_sequence_len_bits(&nums).div_ceil(8) + 1];
+        let mut writer = ExpGolombEncoder::new(&mut buf, start).unwrap();
+
+        for &num in &nums {
+            writer.put_unsigned(num).unwrap();
+        }
+        writer.close();
+
+        let mut reader = ExpGolombDecoder::new(&buf, start).unwrap();
+        for &num in &nums {
+            assert_eq!(reader.next_unsigned(), Some(num));
+        }
+    }
+}
+
+#[test]
+fn put_repeated_bit_matches_bit_loop() {
+    for start in 0..=7 {
+        for count in 0..=23 {
+            for value in [false, true] {
+                let mut expected = [0u8; 4];
+                let mut writer = ExpGolombEncoder::new(&mut expected, start).unwrap();
+                writer.put_bit(true).unwrap();
+                for _ in 0..count {
+                    writer.put_bit(value).unwrap();
+                }
+                writer.put_bit(true).unwrap();
+                let expected_end = writer.close();
+
+                let mut buf = [0u8; 4];
+                let mut writer = ExpGolombEncoder::new(&mut buf, start).unwrap();
+                writer.put_bit(true).unwrap();
+                writer.put_repeated_bit(value, count).unwrap();
+                writer.put_bit(true).unwrap();
+                assert_eq!(writer.close(), expected_end);
+                assert_eq!(buf, expected);
+            }
+        }
+    }
+
+    let mut buf = [0u8; 2];
+    let mut writer = ExpGolombEncoder::new(&mut buf, 5).unwrap();
+    assert!(writer.put_repeated_bit(true, 12).is_none());
+    writer.put_repeated_bit(true, 11).unwrap();
+    writer.put_repeated_bit(true, 0).unwrap();
+    assert!(writer.put_repeated_bit(false, 1).is_none());
+    assert_eq!(writer.close(), (2, 0));
+    assert_eq!(buf, [0b00000111, 0b11111111]);
+}
+
+#[test]
+fn encode_start_7_crossing_into_second_byte() {
+    // The prefix fills the last bit of the first byte and the rest starts on a byte boundary
+    let cases: [(u64, &[u8], (usize, u32)); 4] = [
+        (0, &[0b00000001, 0b00000000, 0b00000000], (1, 0)),
+        (1, &[0b00000000, 0b10000000, 0b00000000], (1, 2)),
+        (5, &[0b00000000, 0b01100000, 0b00000000], (1, 4)),
+        (510, &[0b00000000, 0b00000001, 0b11111111], (3, 0)),
+    ];
+    for (value, expected, end) in cases {
+        let mut buf = [0u8; 3];
+        let mut writer = ExpGolombEncoder::new(&mut buf, 7).unwrap();
+        writer.put_unsigned(value).unwrap();
+        assert_eq!(writer.close(), end);
+        assert_eq!(buf, expected);
+    }
 
+    // Compare against writing the codeword one bit at a time
+    let mut values = vec![u64::MAX];
+    for shift in 0..64 {
+        values.extend([(1 << shift) - 1, 1 << shift, (1 << shift) + 1]);
+    }
+    for value in values {
+        let mut expected = [0u8; 18];
+        let mut writer = ExpGolombEncoder::new(&mut expected, 7).unwrap();
+        let len = unsigned_encoded_len(value);
+        let num_zeros = len / 2;
+        for _ in 0..num_zeros {
+            writer.put_bit(false).unwrap();
+        }
+        writer.put_bit(true).unwrap();
+        // The bits after the leading one of `value + 1`, which wraps to 0 for `u64::MAX`
+        let xp1 = value.wrapping_add(1);
+        for i in (0..num_zeros).rev() {
+            writer.put_bit((xp1 >> i) & 1 == 1).unwrap();
+        }
+        let expected_end = writer.close();
+
+        let mut buf = [0u8; 18];
+        let mut writer = ExpGolombEncoder::new(&mut buf, 7).unwrap();
+        writer.put_unsigned(value).unwrap();
+        assert_eq!(writer.close(), expected_end, "value {value}");
+        assert_eq!(buf, expected, "value {value}");
+    }
+}
+
+#[test]
+fn encode_bit_positions_stay_in_range() {
+    const SEED: u64 = 1;
+    const NUM_VALS: usize = 2000;
+
+    // `put_bytes` debug-asserts that its bit positions stay within a byte, so this exercises it
+    // with every value length and alignment
+    let mut rng = rand::rngs::StdRng::seed_from_u64(SEED);
+    let nums: Vec<u64> = (0..NUM_VALS)
+        .map(|_| rng.gen::<u64>() >> rng.gen_range(0..64))
+        .collect();
+
+    let fixed_bits: usize = nums.iter().map(|num| (num % 65) as usize).sum();
+    let len = (unsigned_sequence_len_bits(&nums) + fixed_bits).div_ceil(8) + 2;
+
+    for start in 0..=7 {
+        for offset in 0..8 {
+            let mut buf = vec![0u8; len];
+            let mut writer = ExpGolombEncoder::new(&mut buf, start).unwrap();
+            writer.put_repeated_bit(false, offset).unwrap();
+            for &num in &nums {
+                writer.put_unsigned(num).unwrap();
+                writer.put_n_bits(num, (num % 65) as u32).unwrap();
+            }
+
+            let mut reader = ExpGolombDecoder::new(&buf, start).unwrap();
+            assert_eq!(reader.next_n_bits(offset), Some(0));
+            for &num in &nums {
+                let num_bits = (num % 65) as u32;
+                let mask = u64::MAX.checked_shr(u64::BITS - num_bits).unwrap_or(0);
+                assert_eq!(reader.next_unsigned(), Some(num));
+                assert_eq!(reader.next_n_bits(num_bits), Some(num & mask));
+            }
+        }
+    }
+}
+
+#[test]
+fn signed_conventions() {
+    let codes = [0u64, 1, 2, 3, 4, u64::MAX - 2, u64::MAX - 1, u64::MAX];
+    let positive_first = [0i64, 1, -1, 2, -2, i64::MAX, -i64::MAX, i64::MIN];
+    let negative_first = [0i64, -1, 1, -2, 2, -i64::MAX, i64::MAX, i64::MIN];
+
+    let mut buf = vec![0u8; unsigned_sequence_len_bits(&codes).div_ceil(8)];
+    let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    for &code in &codes {
+        writer.put_unsigned(code).unwrap();
+    }
+    writer.close();
+
+    let mut reader = ExpGolombDecoder::new(&buf, 0).unwrap();
+    for &value in &positive_first {
+        assert_eq!(reader.next_signed(), Some(value));
+    }
+    let mut reader = ExpGolombDecoder::new(&buf, 0).unwrap();
+    for &value in &negative_first {
+        assert_eq!(reader.next_signed_neg_first(), Some(value));
+    }
+
+    let mut neg_buf = vec![0u8; buf.len()];
+    let mut writer = ExpGolombEncoder::new(&mut neg_buf, 0).unwrap();
+    for &value in &negative_first {
+        writer.put_signed_neg_first(value).unwrap();
+    }
+    writer.close();
+    assert_eq!(neg_buf, buf);
+}
+
+#[test]
+fn signed_code_mapping_is_a_bijection() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+    let mut codes: Vec<u64> = (0..1000).collect();
+    codes.extend([u64::MAX - 2, u64::MAX - 1, u64::MAX]);
+    codes.extend((0..1000).map(|_| rng.gen::<u64>()));
+
+    for code in codes {
+        let value = code_to_signed(code);
+        assert_eq!(signed_to_code(value), code);
+        assert_eq!(signed_encoded_len(value), unsigned_encoded_len(code));
+    }
+}
+
+#[test]
+fn bits_equal_at_every_length() {
+    let a = [0b10110011, 0b01011100, 0b11110000];
+    for diff_bit in 0..24 {
+        let mut b = a;
+        b[diff_bit / 8] ^= 1 << (7 - diff_bit % 8);
+        for num_bits in 0..=24 {
+            assert_eq!(bits_equal(&a, &b, num_bits), num_bits <= diff_bit);
+        }
+        assert!(!bits_equal(&a, &b, 25));
+    }
+}
+
+#[test]
+fn decode_stats_wraps_unsigned_sum() {
+    let nums = [u64::MAX, 3, u64::MAX];
+    let mut buf = vec![0u8; unsigned_sequence_len_bits(&nums).div_ceil(8)];
+    let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
     for &num in &nums {
         writer.put_unsigned(num).unwrap();
     }
     writer.close();
+    assert_eq!(decode_stats_unsigned(&buf, 0), (1, 3));
 
+    let nums = [i64::MIN, i64::MIN, -1];
+    let mut buf = vec![0u8; signed_sequence_len_bits(&nums).div_ceil(8)];
+    let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    for &num in &nums {
+        writer.put_signed(num).unwrap();
+    }
+    writer.close();
+    assert_eq!(decode_stats_signed(&buf, 0), (2 * i64::MIN as i128 - 1, 3));
+}
+
+#[test]
+fn aligned_put_n_bits() {
+    let mut buf = [0u8; 11];
+    let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    writer.put_n_bits(0xAB, 8).unwrap();
+    writer.put_n_bits(0x0123_4567_89AB_CDEF, 64).unwrap();
+    assert!(writer.put_n_bits(0xFF_FFFF, 24).is_none());
+    writer.put_n_bits(0xCD, 8).unwrap();
+    writer.put_bit(true).unwrap();
+    assert!(writer.put_n_bits(0xFF, 8).is_none());
+    assert_eq!(writer.close(), (10, 1));
+    assert_eq!(
+        buf,
+        [0xAB, 0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0xCD, 0b10000000]
+    );
+}
+
+#[test]
+fn put_n_bits_ignores_high_bits() {
+    let mut buf = [0u8; 2];
+    let mut writer = ExpGolombEncoder::new(&mut buf, 4).unwrap();
+    writer.put_n_bits(0xFF, 4).unwrap();
+    writer.put_n_bits(u64::MAX, 0).unwrap();
+    writer.put_n_bits(0b1010_0110, 5).unwrap();
+    writer.close();
+    assert_eq!(buf, [0b00001111, 0b00110000]);
+
+    let mut buf = [0u8; 1];
+    let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    writer.put_bit(false).unwrap();
+    writer.put_n_bits(0xFFFF, 3).unwrap();
+    writer.close();
+    assert_eq!(buf, [0b01110000]);
+}
+
+#[test]
+fn minus_one_round_trip() {
+    let nums = [1, 2, 6, 1 << 32, u64::MAX];
+    let mut buf = [0u8; 32];
+    let mut writer = ExpGolombEncoder::new(&mut buf, 3).unwrap();
+    for &num in &nums {
+        writer
+            .put_unsigned_minus_one(std::num::NonZeroU64::new(num).unwrap())
+            .unwrap();
+    }
+    writer.close();
+
+    let mut reader = ExpGolombDecoder::new(&buf, 3).unwrap();
+    for &num in &nums {
+        assert_eq!(reader.next_unsigned_plus_one().map(|v| v.get()), Some(num));
+    }
+}
+
+#[test]
+fn rollback_to_every_checkpoint() {
+    let nums = [0u64, 5, 510, 1, 1 << 40, 3, 0];
+
+    for start in 0..=7 {
+        let mut expected = Vec::new();
+        for count in 0..=nums.len() {
+            let mut buf = [0u8; 16];
+            let mut writer = ExpGolombEncoder::new(&mut buf, start).unwrap();
+            for &num in &nums[..count] {
+                writer.put_unsigned(num).unwrap();
+            }
+            let end = writer.close();
+            expected.push((buf, end));
+        }
+
+        for count in 0..=nums.len() {
+            let mut buf = [0u8; 16];
+            let mut writer = ExpGolombEncoder::new(&mut buf, start).unwrap();
+            for &num in &nums[..count] {
+                writer.put_unsigned(num).unwrap();
+            }
+            let checkpoint = writer.state();
+            for &num in &nums[count..] {
+                writer.put_unsigned(num).unwrap();
+            }
+            let later = writer.state();
+            writer.rollback(checkpoint).unwrap();
+            assert_eq!(writer.rollback(later).is_some(), later == checkpoint);
+            let end = writer.close();
+            assert_eq!((buf, end), expected[count]);
+        }
+    }
+}
+
+#[test]
+fn escaped_round_trip() {
+    let nums = [0u64, 1, 2, 3, 4, 5, 100, 1 << 20, u64::MAX - 1, u64::MAX];
+    for (threshold, escape_bits) in [(0, 64), (1, 64), (4, 64), (1 << 20, 64), (u64::MAX, 1)] {
+        let mut buf = [0u8; 128];
+        let mut writer = ExpGolombEncoder::new(&mut buf, 5).unwrap();
+        for &num in &nums {
+            writer
+                .put_unsigned_escaped(num, threshold, escape_bits)
+                .unwrap();
+        }
+        writer.close();
+
+        let mut reader = ExpGolombDecoder::new(&buf, 5).unwrap();
+        for &num in &nums {
+            assert_eq!(
+                reader.next_unsigned_escaped(threshold, escape_bits),
+                Some(num),
+                "threshold {threshold}"
+            );
+        }
+    }
+
+    // Regular codewords of values that should have been escaped are rejected
+    let mut buf = [0u8; 1];
+    let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    writer.put_unsigned(5).unwrap();
+    writer.close();
     let mut reader = ExpGolombDecoder::new(&buf, 0).unwrap();
+    assert_eq!(reader.next_unsigned_escaped(5, 8), None);
+    assert_eq!(reader.position(), BitPosition::new(buf.len(), 0));
+
+    // 64 zeros followed by an escaped value of `u64::MAX + 1`
+    let data = [0, 0, 0, 0, 0, 0, 0, 0, 0b10000000];
+    let mut reader = ExpGolombDecoder::new(&data, 0).unwrap();
+    assert_eq!(reader.next_unsigned_escaped(u64::MAX, 1), None);
+    assert_eq!(reader.position(), BitPosition::new(data.len(), 0));
+}
+
+#[test]
+fn fixed_point_precision() {
+    let nums = [u64::MAX, (1 << 53) + 1, 3];
+    let mut buf = vec![0u8; unsigned_sequence_len_bits(&nums).div_ceil(8)];
+    let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
     for &num in &nums {
-        assert_eq!(reader.next_unsigned(), Some(num));
+        writer.put_unsigned(num).unwrap();
+    }
+    writer.close();
+
+    let mut reader = ExpGolombDecoder::new(&buf, 0).unwrap();
+    // Rounds to 2^64 before scaling
+    assert_eq!(reader.next_unsigned_fixed_point(64), Some(1.0));
+    // Rounds to the nearest even integer, 2^53
+    assert_eq!(reader.next_unsigned_fixed_point(53), Some(1.0));
+    assert_eq!(reader.next_unsigned_fixed_point(65), None);
+
+    let mut reader = ExpGolombDecoder::new(&buf, 0).unwrap();
+    assert_eq!(reader.next_signed_fixed_point(63), Some(-1.0));
+}
+
+#[test]
+fn drained_stream_matches_direct_encode() {
+    let values: Vec<u64> = (0..500u64).map(|i| i * i % 1000).collect();
+    let mut expected = vec![0u8; 1024];
+    let mut writer = ExpGolombEncoder::new(&mut expected, 3).unwrap();
+    for &value in &values {
+        writer.put_unsigned(value).unwrap();
+    }
+    let (index, bit_pos) = writer.close();
+    expected.truncate(index + (bit_pos != 0) as usize);
+
+    let mut buf = [0u8; 8];
+    let mut writer = ExpGolombEncoder::new(&mut buf, 3).unwrap();
+    let mut out = Vec::new();
+    for &value in &values {
+        if writer.put_unsigned(value).is_none() {
+            out.extend_from_slice(writer.drain_complete_bytes());
+            writer.put_unsigned(value).unwrap();
+        }
+    }
+    out.extend_from_slice(writer.drain_complete_bytes());
+    let (index, bit_pos) = writer.close();
+    out.extend_from_slice(&buf[index..index + (bit_pos != 0) as usize]);
+    assert_eq!(out, expected);
+}
+
+#[test]
+fn reset_keeps_bits_before_start() {
+    let mut buf = [0b10100000, 0, 0];
+    let mut writer = ExpGolombEncoder::new(&mut buf, 3).unwrap();
+    writer.put_unsigned(510).unwrap();
+    writer.reset(3).unwrap();
+    writer.put_unsigned(0).unwrap();
+    assert_eq!(writer.close(), (0, 4));
+    assert_eq!(buf, [0b10110000, 0, 0]);
+
+    // Within the first byte
+    let mut buf = [0b11000000];
+    let mut writer = ExpGolombEncoder::new(&mut buf, 2).unwrap();
+    writer.put_n_bits(0b111111, 6).unwrap();
+    writer.reset(2).unwrap();
+    assert_eq!(writer.close(), (0, 2));
+    assert_eq!(buf, [0b11000000]);
+}
+
+#[test]
+fn rollback_to_state_saved_after_drain() {
+    let mut buf = [0u8; 2];
+    let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    writer.put_n_bits(0xAB, 8).unwrap();
+    writer.put_bit(true).unwrap();
+    assert_eq!(writer.drain_complete_bytes(), [0xAB]);
+
+    let state = writer.state();
+    assert_eq!((state.index(), state.bit_pos()), (0, 1));
+    assert_eq!(writer.position_bits(), 1);
+
+    // Compacts the buffer before writing
+    writer.put_unsigned(5).unwrap();
+    writer.rollback(state).unwrap();
+    assert_eq!(writer.position_bits(), 1);
+    writer.put_unsigned(0).unwrap();
+    assert_eq!(writer.close(), (0, 2));
+    assert_eq!(buf, [0b11000000, 0]);
+}
+
+#[test]
+fn raw_codeword_ignores_high_bits() {
+    let mut buf = [0u8; 1];
+    let mut writer = ExpGolombEncoder::new(&mut buf, 0).unwrap();
+    writer.put_bit(false).unwrap();
+    writer.put_raw_codeword(u128::MAX, 3).unwrap();
+    writer.close();
+    assert_eq!(buf, [0b01110000]);
+
+    let mut buf = [0u8; 1];
+    let mut writer = ExpGolombEncoder::new(&mut buf, 4).unwrap();
+    writer.put_raw_codeword(0b1111_0001, 4).unwrap();
+    writer.close();
+    assert_eq!(buf, [0b00000001]);
+}
+
+#[test]
+fn raw_codeword_passthrough() {
+    let values = [0, 1, 5, 510, 1 << 40, u64::MAX - 1, u64::MAX];
+    for start in 0..=7 {
+        let mut src = vec![0u8; unsigned_sequence_len_bits(&values).div_ceil(8) + 1];
+        let mut writer = ExpGolombEncoder::new(&mut src, start).unwrap();
+        for &value in &values {
+            writer.put_unsigned(value).unwrap();
+        }
+        writer.close();
+
+        let mut dst = vec![0u8; src.len()];
+        let mut reader = ExpGolombDecoder::new(&src, start).unwrap();
+        let mut writer = ExpGolombEncoder::new(&mut dst, start).unwrap();
+        while let Some((_, bits, len)) = reader.next_unsigned_raw() {
+            writer.put_raw_codeword(bits, len).unwrap();
+        }
+        writer.close();
+        assert_eq!(dst, src);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn zero_runs_round_trip() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+    for _ in 0..200 {
+        let len = rng.gen_range(0..100);
+        let values: Vec<u64> = (0..len)
+            .map(|_| {
+                if rng.gen_bool(0.8) {
+                    0
+                } else {
+                    rng.gen::<u64>() >> rng.gen_range(0..64)
+                }
+            })
+            .collect();
+        let buf = encode_with_zero_runs(&values);
+        assert_eq!(decode_with_zero_runs(&buf, 0, len).unwrap(), values);
+        if len > 0 {
+            assert_eq!(decode_with_zero_runs(&buf, 0, len - 1), None);
+        }
     }
 }
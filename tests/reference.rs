@@ -0,0 +1,163 @@
+//! Differential tests against a naive bit-by-bit implementation of Exp-Golomb coding.
+
+use exp_golomb::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const SEED: u64 = 0;
+
+/// Encode `values` one bit at a time after `start` padding bits.
+fn reference_encode(values: &[u64], start: u32) -> Vec<u8> {
+    let mut bits = vec![false; start as usize];
+    for &value in values {
+        let xp1 = value as u128 + 1;
+        let len = u128::BITS - xp1.leading_zeros();
+        bits.extend((1..len).map(|_| false));
+        bits.extend((0..len).rev().map(|i| (xp1 >> i) & 1 == 1));
+    }
+    pack(&bits)
+}
+
+/// Decode the values in `buf` one bit at a time, returning each value with the bit offset one past
+/// its codeword.
+fn reference_decode(buf: &[u8], start: u32) -> Vec<(u64, usize)> {
+    let bits = unpack(buf);
+    let mut values = Vec::new();
+    let mut pos = start as usize;
+    loop {
+        let mut lz = 0;
+        while bits.get(pos) == Some(&false) {
+            lz += 1;
+            pos += 1;
+        }
+        if pos + lz >= bits.len() || lz > 64 {
+            return values;
+        }
+        pos += 1;
+
+        let mut value: u128 = 1;
+        for _ in 0..lz {
+            value = (value << 1) | bits[pos] as u128;
+            pos += 1;
+        }
+        match u64::try_from(value - 1) {
+            Ok(value) => values.push((value, pos)),
+            Err(_) => return values,
+        }
+    }
+}
+
+fn pack(bits: &[bool]) -> Vec<u8> {
+    let mut bytes = vec![0u8; bits.len().div_ceil(8)];
+    for (i, &bit) in bits.iter().enumerate() {
+        bytes[i / 8] |= (bit as u8) << (7 - i % 8);
+    }
+    bytes
+}
+
+fn unpack(buf: &[u8]) -> Vec<bool> {
+    (0..buf.len() * 8)
+        .map(|i| (buf[i / 8] >> (7 - i % 8)) & 1 == 1)
+        .collect()
+}
+
+/// Values of every length, biased towards short codewords.
+fn random_values(rng: &mut StdRng, count: usize) -> Vec<u64> {
+    let mut values = vec![0, 1, u64::MAX - 1, u64::MAX];
+    values.extend((0..count).map(|_| rng.gen::<u64>() >> rng.gen_range(0..64)));
+    values.extend((0..count).map(|_| rng.gen_range(0..16)));
+    values
+}
+
+/// Bytes with long runs of zeros so that long codewords show up.
+fn random_bytes(rng: &mut StdRng, len: usize) -> Vec<u8> {
+    (0..len)
+        .map(|_| {
+            if rng.gen_bool(0.3) {
+                0
+            } else {
+                rng.gen::<u8>() >> rng.gen_range(0..8)
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn encoder_matches_reference() {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let values = random_values(&mut rng, 2000);
+
+    for start in 0..=7 {
+        let expected = reference_encode(&values, start);
+        let mut buf = vec![0u8; expected.len()];
+        let mut writer = ExpGolombEncoder::new(&mut buf, start).unwrap();
+        for &value in &values {
+            writer.put_unsigned(value).unwrap();
+        }
+        writer.close();
+        assert_eq!(buf, expected);
+    }
+}
+
+#[test]
+fn decoder_matches_reference() {
+    let mut rng = StdRng::seed_from_u64(SEED);
+
+    for _ in 0..200 {
+        let len = rng.gen_range(1..64);
+        let buf = random_bytes(&mut rng, len);
+
+        for start in 0..=7 {
+            let expected = reference_decode(&buf, start);
+            let mut reader = ExpGolombDecoder::new(&buf, start).unwrap();
+            let mut skipper = ExpGolombDecoder::new(&buf, start).unwrap();
+            let mut prev_end = start as usize;
+            for &(value, end) in &expected {
+                assert_eq!(reader.next_unsigned(), Some(value));
+                let pos = reader.position();
+                assert_eq!(pos.byte_index * 8 + pos.bit_offset as usize, end);
+
+                assert_eq!(skipper.skip_next_len(), Some((end - prev_end) as u32));
+                assert_eq!(skipper.position(), reader.position());
+                prev_end = end;
+            }
+            assert_eq!(reader.next_unsigned(), None);
+        }
+    }
+}
+
+#[test]
+fn expected_len_matches_reference() {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let values = random_values(&mut rng, 500);
+    let buf = reference_encode(&values, 0);
+
+    let mut reader = ExpGolombDecoder::new(&buf, 0).unwrap();
+    let mut prev_end = 0;
+    for (value, end) in reference_decode(&buf, 0) {
+        let len = (end - prev_end) as u32;
+        assert_eq!(reader.next_unsigned_expect_len(len + 2), None);
+        assert_eq!(reader.next_unsigned_expect_len(len.saturating_sub(2)), None);
+        assert_eq!(reader.next_unsigned_expect_len(len), Some(value));
+        prev_end = end;
+    }
+}
+
+#[test]
+fn round_trip_through_reference() {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let values = random_values(&mut rng, 2000);
+
+    for start in 0..=7 {
+        let buf = reference_encode(&values, start);
+        let mut reader = ExpGolombDecoder::new(&buf, start).unwrap();
+        for &value in &values {
+            assert_eq!(reader.next_unsigned(), Some(value));
+        }
+
+        let decoded: Vec<u64> = reference_decode(&buf, start)
+            .into_iter()
+            .map(|(value, _)| value)
+            .collect();
+        assert_eq!(decoded, values);
+    }
+}